@@ -2,10 +2,10 @@
 
 use sealed::sealed;
 
-/// Generates a new ZST with the prescribed alignment.
+/// Generates new ZSTs with the prescribed alignments.
 macro_rules! align_marker {
-    ($align:literal) => {
-        ::paste::paste!{
+    ($($align:literal),*) => {
+        $(::paste::paste!{
             #[doc = concat!("ZST with an alignment of ", stringify!($align), " bytes.")]
             #[derive(Default, Copy, Clone, Debug)]
             #[repr(align($align))]
@@ -14,46 +14,45 @@ macro_rules! align_marker {
             #[sealed]
             impl crate::align::valid_alignment::ValidAlignment for [<AlignMarker $align>]  {
                 const MARKER: Self::Marker = [<AlignMarker $align>];
+                const VALUE: usize = $align;
                 type Marker = [<AlignMarker $align>];
+                type Alignment = crate::align::alignment::Alignment<$align>;
             }
             
             #[sealed]
             impl crate::align::valid_alignment::ValidAlignment for crate::align::alignment::Alignment<$align>  {
                 const MARKER: Self::Marker = [<AlignMarker $align>];
+                const VALUE: usize = $align;
                 type Marker = [<AlignMarker $align>];
+                type Alignment = crate::align::alignment::Alignment<$align>;
             }
-        }
+        })*
     };
 }
 
-// FIXME: Restrict higher alignments on 16-bit platforms
-align_marker!(1);
-align_marker!(2);
-align_marker!(4);
-align_marker!(8);
-align_marker!(16);
-align_marker!(32);
-align_marker!(64);
-align_marker!(128);
-align_marker!(256);
-align_marker!(512);
-align_marker!(1024);
-align_marker!(2048);
-align_marker!(4096);
-align_marker!(8192);
-align_marker!(16384);
-align_marker!(32768);
-align_marker!(65536);
-align_marker!(131072);
-align_marker!(262144);
-align_marker!(524288);
-align_marker!(1048576);
-align_marker!(2097152);
-align_marker!(4194304);
-align_marker!(8388608);
-align_marker!(16777216);
-align_marker!(33554432);
-align_marker!(67108864);
-align_marker!(134217728);
-align_marker!(268435456);
-align_marker!(536870912);
+align_marker!(
+    1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096, 8192, 16384
+);
+
+// Values can be at most `isize::MAX` bytes (and a value is at least as big as its alignment), so
+// higher alignments are impossible on 16-bit platforms.
+#[cfg(not(target_pointer_width = "16"))]
+align_marker!(
+    32768, 65536, 131072, 262144, 524288, 1048576, 2097152, 4194304, 8388608, 16777216, 33554432,
+    67108864, 134217728, 268435456, 536870912
+);
+
+/// The alignment of a cache-line, e.g. to avoid false sharing between values which are written
+/// concurrently (see [CachePadded](crate::align::CachePadded)).
+/// 
+/// On x86_64 and aarch64, this is the alignment of a pair of (64-byte) cache-lines, as the
+/// spatial prefetcher fetches cache-lines in pairs.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub type CacheLineAlignment = AlignMarker128;
+
+/// The alignment of a cache-line, e.g. to avoid false sharing between values which are written
+/// concurrently (see [CachePadded](crate::align::CachePadded)).
+/// 
+/// On this architecture, this is 64 bytes, the most common cache-line size.
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub type CacheLineAlignment = AlignMarker64;
@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use crate::align::Alignment;
+use crate::align::valid_alignment::ValidAlignment;
+
+/// A value of any type `T` that is aligned to (at least) `ALIGN` bytes.
+///
+/// The generic counterpart of [AlignedBytes](crate::align::AlignedBytes), e.g. to keep values
+/// which are written concurrently on separate cache-lines, or to align SIMD lanes. The value is
+/// at the start of the struct, which is padded to a multiple of `ALIGN` bytes.
+///
+/// Generic Parameters:
+/// - `T`: the type of the aligned value.
+/// - `ALIGN`: the minimum alignment of the value. Must be a [valid alignment](ValidAlignment).
+#[repr(C)]
+pub struct Aligned<T, const ALIGN: usize>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    value: T,
+    _align: Alignment<ALIGN>
+}
+
+impl<T, const ALIGN: usize> Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Aligns the given value.
+    pub const fn new(value: T) -> Self {
+        Aligned {
+            value,
+            _align: Alignment::new()
+        }
+    }
+
+    /// Returns the aligned value (which is no longer guaranteed to be aligned).
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Borrows the aligned value.
+    pub const fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Mutably borrows the aligned value.
+    pub const fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T, const ALIGN: usize> Deref for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T, const ALIGN: usize> DerefMut for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T, const ALIGN: usize> From<T> for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+// The traits below are implemented manually (rather than derived), as the derives would require
+// them of the alignment marker too.
+
+impl<T: Clone, const ALIGN: usize> Clone for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T: Copy, const ALIGN: usize> Copy for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+impl<T: Default, const ALIGN: usize> Default for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Debug, const ALIGN: usize> Debug for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Aligned")
+            .field(&self.value)
+            .finish()
+    }
+}
+
+impl<T: PartialEq, const ALIGN: usize> PartialEq for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, const ALIGN: usize> Eq for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+impl<T: PartialOrd, const ALIGN: usize> PartialOrd for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord, const ALIGN: usize> Ord for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: Hash, const ALIGN: usize> Hash for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::align::Aligned;
+
+    #[test]
+    fn aligned() {
+        assert_eq!(align_of::<Aligned<u8, 64>>(), 64);
+        assert_eq!(size_of::<Aligned<u8, 64>>(), 64);
+
+        // Never less aligned than the value
+        assert_eq!(align_of::<Aligned<u64, 1>>(), align_of::<u64>());
+        assert_eq!(size_of::<Aligned<[u64; 3], 16>>(), 32);
+
+        let counters: [Aligned<AtomicUsize, 128>; 2] = Default::default();
+
+        counters[1].fetch_add(1, Ordering::Relaxed);
+
+        let first = &*counters[0] as *const AtomicUsize as usize;
+        let second = &*counters[1] as *const AtomicUsize as usize;
+
+        assert_eq!(first % 128, 0);
+        assert_eq!(second - first, 128);
+        assert_eq!(counters[1].load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn forwards_traits() {
+        let lanes = Aligned::<[f32; 4], 16>::new([1.0, 2.0, 3.0, 4.0]);
+        let copy = lanes;
+
+        assert_eq!(lanes, copy);
+        assert_eq!(format!("{:?}", lanes), "Aligned([1.0, 2.0, 3.0, 4.0])");
+
+        let set: HashSet<Aligned<u32, 8>> = [1, 2, 2, 3].into_iter().map(Aligned::from).collect();
+
+        assert_eq!(set.len(), 3);
+        assert!(Aligned::<u32, 8>::new(1) < Aligned::new(2));
+        assert_eq!(Aligned::<String, 32>::new(String::from("test")).into_inner(), "test");
+    }
+}
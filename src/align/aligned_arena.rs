@@ -0,0 +1,246 @@
+use std::alloc::{GlobalAlloc, Layout};
+use std::cell::UnsafeCell;
+use std::ptr::{null_mut, NonNull};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::align::{AlignedBytes, Alignment};
+use crate::align::valid_alignment::ValidAlignment;
+use crate::erasure::AllocError;
+
+/// A fixed-capacity bump allocator, which hands out chunks of an [AlignedBytes] buffer, e.g. for
+/// `no_std`-style or test contexts.
+///
+/// Chunks are allocated from the start of the buffer to its end. Deallocating a chunk only
+/// reclaims its memory if it was the most recent allocation (which can also be grown in place),
+/// otherwise the memory is reclaimed when the arena is [reset](AlignedArena::reset). Allocations
+/// which don't fit in the remainder of the buffer, or which are aligned to more than `ALIGN`
+/// bytes, fail (returning null for [GlobalAlloc]).
+///
+/// The arena can be used as the `#[global_allocator]`, in which case the erasure containers
+/// (e.g. [AutoDropUnique](crate::erasure::AutoDropUnique)) allocate from it, and
+/// [TryErase](crate::erasure::TryErase) returns an error if it is exhausted:
+/// ```
+/// use dynrsaur::align::{AlignedArena, Aligned};
+/// use dynrsaur::erasure::{AutoDropUnique, TryErase};
+///
+/// #[global_allocator]
+/// static ARENA: AlignedArena<{ 1 << 20 }, 4096> = AlignedArena::new();
+///
+/// assert!(AutoDropUnique::try_erase([0u8; 1024]).is_ok());
+/// assert!(AutoDropUnique::try_erase([0u8; 1 << 20]).is_err());
+/// assert!(AutoDropUnique::try_erase(Aligned::<u8, 8192>::new(0)).is_err());
+/// ```
+///
+/// Generic Parameters:
+/// - `SIZE`: the capacity of the arena, in bytes.
+/// - `ALIGN`: the alignment of the buffer (and the maximum alignment of allocations). Must be a
+///   [valid alignment](ValidAlignment).
+pub struct AlignedArena<const SIZE: usize, const ALIGN: usize>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// The memory of the allocations.
+    buffer: UnsafeCell<AlignedBytes<SIZE, ALIGN>>,
+    /// The offset of the first unallocated byte.
+    next: AtomicUsize
+}
+
+impl<const SIZE: usize, const ALIGN: usize> AlignedArena<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Creates an arena with no allocations.
+    pub const fn new() -> Self {
+        AlignedArena {
+            buffer: UnsafeCell::new(AlignedBytes::zeroed()),
+            next: AtomicUsize::new(0)
+        }
+    }
+
+    /// The capacity of the arena, in bytes.
+    pub const fn capacity(&self) -> usize {
+        SIZE
+    }
+
+    /// The number of bytes which have been allocated (including any padding between allocations).
+    pub fn used(&self) -> usize {
+        self.next.load(Ordering::Acquire)
+    }
+
+    /// Reclaims the memory of all allocations.
+    ///
+    /// Exclusive access ensures that no allocations are borrowed, but they mustn't be used
+    /// (e.g. by a `Box` holding a pointer into the arena) after the reset.
+    pub fn reset(&mut self) {
+        *self.next.get_mut() = 0
+    }
+
+    /// Allocates a chunk of memory for the `layout`, or returns an error if it doesn't fit in the
+    /// remainder of the arena, or is aligned to more than `ALIGN` bytes.
+    pub fn try_alloc(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        if layout.align() > ALIGN {
+            return Err(AllocError::new(layout));
+        }
+
+        let mut next = self.next.load(Ordering::Acquire);
+
+        loop {
+            let start = next.checked_next_multiple_of(layout.align());
+            let end = start.and_then(|start| start.checked_add(layout.size()));
+
+            let (Some(start), Some(end)) = (start, end) else {
+                return Err(AllocError::new(layout));
+            };
+
+            if end > SIZE {
+                return Err(AllocError::new(layout));
+            }
+
+            match self.next.compare_exchange_weak(next, end, Ordering::AcqRel, Ordering::Acquire) {
+                // SAFETY: `start` is within (or one past the end of) the buffer.
+                Ok(_) => return Ok(unsafe { self.base().add(start) }),
+                Err(actual) => next = actual
+            }
+        }
+    }
+
+    /// The start of the buffer.
+    fn base(&self) -> NonNull<u8> {
+        // SAFETY: Pointers to cell contents are non-null.
+        unsafe { NonNull::new_unchecked(self.buffer.get() as *mut u8) }
+    }
+
+    /// The offset of `ptr` (allocated by this arena) into the buffer.
+    fn offset_of(&self, ptr: *mut u8) -> usize {
+        ptr as usize - self.base().as_ptr() as usize
+    }
+}
+
+impl<const SIZE: usize, const ALIGN: usize> Default for AlignedArena<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SAFETY: The buffer is only accessed through disjoint allocations, which are claimed atomically.
+unsafe impl<const SIZE: usize, const ALIGN: usize> Sync for AlignedArena<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+// SAFETY: Allocations are disjoint chunks of the buffer, aligned for their layout, which remain
+//         valid until deallocated (as the arena can't be reset while borrowed).
+unsafe impl<const SIZE: usize, const ALIGN: usize> GlobalAlloc for AlignedArena<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.try_alloc(layout).map_or(null_mut(), NonNull::as_ptr)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let start = self.offset_of(ptr);
+
+        // Only reclaims the most recent allocation (if another allocation has since been made,
+        // this fails, and the memory is leaked until reset)
+        let _ = self.next.compare_exchange(
+            start + layout.size(),
+            start,
+            Ordering::AcqRel,
+            Ordering::Relaxed
+        );
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let start = self.offset_of(ptr);
+
+        // Grows (or shrinks) the most recent allocation in place
+        if new_size <= SIZE - start {
+            let resized = self.next.compare_exchange(
+                start + layout.size(),
+                start + new_size,
+                Ordering::AcqRel,
+                Ordering::Relaxed
+            );
+
+            if resized.is_ok() {
+                return ptr;
+            }
+        }
+
+        // SAFETY: The caller guarantees that `new_size` is valid for the alignment.
+        let new_layout = unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) };
+
+        // SAFETY: Forwarded from the caller.
+        let new_ptr = unsafe { self.alloc(new_layout) };
+
+        if !new_ptr.is_null() {
+            // SAFETY: Both allocations are valid for the smaller size, and are disjoint.
+            unsafe {
+                ptr.copy_to_nonoverlapping(new_ptr, layout.size().min(new_size));
+                self.dealloc(ptr, layout);
+            }
+        }
+
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout};
+    use crate::align::AlignedArena;
+
+    #[test]
+    fn bump() {
+        let mut arena = AlignedArena::<256, 64>::new();
+
+        let first = arena.try_alloc(Layout::new::<u8>()).unwrap();
+        let second = arena.try_alloc(Layout::new::<[u64; 4]>()).unwrap();
+
+        assert_eq!(second.as_ptr() as usize - first.as_ptr() as usize, 8);
+        assert_eq!(arena.used(), 40);
+
+        // Only the most recent allocation is reclaimed
+        unsafe {
+            arena.dealloc(first.as_ptr(), Layout::new::<u8>());
+            assert_eq!(arena.used(), 40);
+
+            arena.dealloc(second.as_ptr(), Layout::new::<[u64; 4]>());
+            assert_eq!(arena.used(), 8);
+        }
+
+        assert!(arena.try_alloc(Layout::from_size_align(8, 128).unwrap()).is_err());
+        assert!(arena.try_alloc(Layout::from_size_align(256, 64).unwrap()).is_err());
+        let last = arena.try_alloc(Layout::from_size_align(192, 64).unwrap()).unwrap();
+
+        assert_eq!(last.as_ptr() as usize % 64, 0);
+        assert_eq!(arena.used(), 256);
+
+        arena.reset();
+
+        assert_eq!(arena.used(), 0);
+        assert_eq!(arena.try_alloc(Layout::new::<u8>()).unwrap(), first);
+    }
+
+    #[test]
+    fn realloc() {
+        let arena = AlignedArena::<64, 8>::new();
+        let layout = Layout::new::<[u8; 8]>();
+
+        unsafe {
+            let first = arena.alloc(layout);
+            first.write_bytes(1, 8);
+
+            // Grows in place
+            assert_eq!(arena.realloc(first, layout, 16), first);
+            assert_eq!(arena.used(), 16);
+
+            let second = arena.alloc(layout);
+
+            // Moves
+            let moved = arena.realloc(first, Layout::new::<[u8; 16]>(), 24);
+            assert_eq!(moved, second.add(8));
+            assert_eq!(*moved.cast::<[u8; 8]>(), [1; 8]);
+
+            assert!(arena.realloc(moved, Layout::new::<[u8; 24]>(), 64).is_null());
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::alloc::{alloc, alloc_zeroed};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::ptr::{copy_nonoverlapping, NonNull};
+use crate::align::{AlignedBytes, AlignedVec, Alignment};
+use crate::align::aligned_vec::{allocate, deallocate, dangling};
+use crate::align::valid_alignment::ValidAlignment;
+
+/// An owning pointer to a value on the heap, which is aligned to `ALIGN` bytes.
+///
+/// Currently only implemented for byte-slices (`AlignedBox<[u8], ALIGN>`), the fixed-size
+/// counterpart of [AlignedVec].
+///
+/// Generic Parameters:
+/// - `T`: the type of the boxed value.
+/// - `ALIGN`: the alignment of the value. Must be a [valid alignment](ValidAlignment).
+pub struct AlignedBox<T: ?Sized, const ALIGN: usize>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Pointer to the value (dangling, but aligned, if the value is zero-sized).
+    ptr: NonNull<T>,
+    /// Marker for the alignment of the value (a [PhantomData], as the `AlignedBox` itself
+    /// needn't be aligned).
+    _align: PhantomData<Alignment<ALIGN>>
+}
+
+impl<const ALIGN: usize> AlignedBox<[u8], ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Allocates `len` zero bytes.
+    pub fn zeroed(len: usize) -> Self {
+        // SAFETY: `alloc_zeroed` initialises the bytes, which are owned by the box.
+        unsafe { Self::from_raw_parts(allocate::<ALIGN>(len, alloc_zeroed), len) }
+    }
+
+    /// Allocates a copy of `slice`.
+    pub fn from_slice(slice: &[u8]) -> Self {
+        // SAFETY: The bytes are initialised by the copy, and owned by the box.
+        unsafe {
+            let ptr = allocate::<ALIGN>(slice.len(), alloc);
+
+            copy_nonoverlapping(slice.as_ptr(), ptr.as_ptr(), slice.len());
+
+            Self::from_raw_parts(ptr, slice.len())
+        }
+    }
+
+    /// Converts to a growable buffer (without reallocating).
+    pub fn into_vec(self) -> AlignedVec<ALIGN> {
+        let len = self.len();
+        let boxed = ManuallyDrop::new(self);
+
+        // SAFETY: The allocation (if any) is exactly `len` bytes, and is owned by the vector.
+        unsafe { AlignedVec::from_raw_parts(boxed.ptr.cast(), len, len) }
+    }
+
+    /// Takes ownership of the `len` bytes at `ptr`.
+    ///
+    /// SAFETY: `ptr` must have been [allocated](allocate) with `len` bytes, which are initialised.
+    pub(super) unsafe fn from_raw_parts(ptr: NonNull<u8>, len: usize) -> Self {
+        AlignedBox {
+            ptr: NonNull::slice_from_raw_parts(ptr, len),
+            _align: PhantomData
+        }
+    }
+}
+
+impl<T: ?Sized, const ALIGN: usize> Deref for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The value is valid for the lifetime of the box.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized, const ALIGN: usize> DerefMut for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The value is valid for the lifetime of the box, and uniquely borrowed.
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized, const ALIGN: usize> Drop for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn drop(&mut self) {
+        // SAFETY: The value was allocated by `allocate`, with its size (bytes don't need
+        //         dropping).
+        unsafe { deallocate::<ALIGN>(self.ptr.cast(), size_of_val(self.ptr.as_ref())) }
+    }
+}
+
+// SAFETY: The value is uniquely owned, like `Box<T>`.
+unsafe impl<T: ?Sized + Send, const ALIGN: usize> Send for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+unsafe impl<T: ?Sized + Sync, const ALIGN: usize> Sync for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+impl<const ALIGN: usize> Default for AlignedBox<[u8], ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn default() -> Self {
+        // SAFETY: An empty slice needs no allocation.
+        unsafe { Self::from_raw_parts(dangling::<ALIGN>(), 0) }
+    }
+}
+
+impl<const ALIGN: usize> Clone for AlignedBox<[u8], ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn clone(&self) -> Self {
+        Self::from_slice(self)
+    }
+}
+
+impl<T: ?Sized + Debug, const ALIGN: usize> Debug for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + PartialEq, const ALIGN: usize> PartialEq for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + Eq, const ALIGN: usize> Eq for AlignedBox<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+impl<const ALIGN: usize> From<&[u8]> for AlignedBox<[u8], ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(slice: &[u8]) -> Self {
+        Self::from_slice(slice)
+    }
+}
+
+impl<const ALIGN: usize> From<AlignedVec<ALIGN>> for AlignedBox<[u8], ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(vec: AlignedVec<ALIGN>) -> Self {
+        vec.into_boxed_slice()
+    }
+}
+
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> From<AlignedBytes<SIZE, ALIGN>> for AlignedBox<[u8], ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(bytes: AlignedBytes<SIZE, ALIGN>) -> Self {
+        Self::from_slice(bytes.as_bytes())
+    }
+}
+
+/// Returns the box if its length is not `SIZE`.
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> TryFrom<AlignedBox<[u8], ALIGN>> for AlignedBytes<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    type Error = AlignedBox<[u8], ALIGN>;
+
+    fn try_from(boxed: AlignedBox<[u8], ALIGN>) -> Result<Self, Self::Error> {
+        match <[u8; SIZE]>::try_from(&*boxed) {
+            Ok(bytes) => Ok(AlignedBytes::from(bytes)),
+            Err(_) => Err(boxed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{AlignedBox, AlignedBytes};
+
+    #[test]
+    fn aligned() {
+        let mut boxed = AlignedBox::<[u8], 4096>::zeroed(512);
+
+        assert_eq!(boxed.as_ptr() as usize % 4096, 0);
+        assert!(boxed.iter().all(|byte| *byte == 0));
+
+        boxed[511] = 1;
+
+        let clone = boxed.clone();
+
+        assert_eq!(clone.as_ptr() as usize % 4096, 0);
+        assert_eq!(clone, boxed);
+
+        let empty = AlignedBox::<[u8], 4096>::default();
+
+        assert_eq!(empty.as_ptr() as usize % 4096, 0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn bytes_conversions() {
+        let boxed = AlignedBox::from(AlignedBytes::<4, 64>::from([1, 2, 3, 4]));
+
+        assert_eq!(&*boxed, [1, 2, 3, 4]);
+
+        let Err(boxed) = AlignedBytes::<5, 64>::try_from(boxed) else { panic!("wrong length") };
+        let Ok(bytes) = AlignedBytes::<4, 64>::try_from(boxed) else { panic!("right length") };
+
+        assert_eq!(bytes.as_bytes(), &[1, 2, 3, 4]);
+    }
+}
@@ -1,8 +1,29 @@
 use std::ops::{Deref, DerefMut};
-use crate::align::Alignment;
+use std::slice::{from_raw_parts, from_raw_parts_mut};
+use const_panic::concat_panic;
+use crate::align::{Alignment, AsBytes, FromBytes, Immutable};
 use crate::align::valid_alignment::ValidAlignment;
 
 /// A byte-array (`[u8; SIZE]`) that is aligned to `ALIGN` bytes.
+/// 
+/// Byte-arrays can be composed in const contexts (e.g. `static` initialisers), with
+/// [concat](AlignedBytes::concat), [split_at](AlignedBytes::split_at) and
+/// [sub](AlignedBytes::sub). As stable Rust can't compute sizes in const generics, the sizes of
+/// the results are inferred (or given as `_`), and checked at compile time:
+/// ```
+/// use dynrsaur::align::AlignedBytes;
+/// 
+/// static HEADER: AlignedBytes<4, 4> = AlignedBytes::from(*b"PKT1");
+/// static PACKET: AlignedBytes<8, 4> = HEADER.concat(&AlignedBytes::<4, 4>::from([0, 0, 0, 42]));
+/// static BODY: &AlignedBytes<4, 4> = PACKET.sub::<4, _, _>();
+/// 
+/// let (header, _): (_, AlignedBytes<4, 4>) = PACKET.split_at::<4, _>();
+/// 
+/// assert_eq!(header.as_bytes(), b"PKT1");
+/// assert_eq!(BODY.as_bytes(), &[0, 0, 0, 42]);
+/// ```
+// `repr(C)` so the bytes are at the start (see `sub`)
+#[repr(C)]
 pub struct AlignedBytes<
     const SIZE: usize,
     const ALIGN: usize
@@ -42,6 +63,260 @@ impl<
     pub const fn as_bytes_mut(&mut self) -> &mut [u8; SIZE] {
         &mut self.bytes
     }
+    
+    /// Views the first `size_of::<T>()` bytes as a `T`, without copying.
+    /// 
+    /// Fails to compile if a `T` is bigger, or more aligned, than the byte-array.
+    pub const fn view<T: FromBytes + Immutable>(&self) -> &T {
+        const { Self::check_size_and_align_of::<T>(1) };
+        
+        // SAFETY: The bytes are big enough, and aligned enough, for a `T`, are a valid `T` (as
+        //         any bytes are), and can't be mutated while borrowed.
+        unsafe { &*(self.bytes.as_ptr() as *const T) }
+    }
+    
+    /// Mutably views the first `size_of::<T>()` bytes as a `T`, without copying.
+    /// 
+    /// Fails to compile if a `T` is bigger, or more aligned, than the byte-array.
+    pub const fn view_mut<T: FromBytes + AsBytes>(&mut self) -> &mut T {
+        const { Self::check_size_and_align_of::<T>(1) };
+        
+        // SAFETY: The bytes are big enough, and aligned enough, for a `T`, are a valid `T` (as
+        //         any bytes are), and remain initialised bytes when a `T` is written (as it has
+        //         no padding).
+        unsafe { &mut *(self.bytes.as_mut_ptr() as *mut T) }
+    }
+    
+    /// Views the bytes as a slice of `SIZE / size_of::<T>()` values, without copying.
+    /// 
+    /// Fails to compile if a `T` is zero-sized, or more aligned than the byte-array, or if `SIZE`
+    /// is not a multiple of its size.
+    pub const fn view_slice<T: FromBytes + Immutable>(&self) -> &[T] {
+        const { Self::check_slice_of::<T>() };
+        
+        // SAFETY: As for `view`, for each element.
+        unsafe { from_raw_parts(self.bytes.as_ptr() as *const T, SIZE / size_of::<T>()) }
+    }
+    
+    /// Mutably views the bytes as a slice of `SIZE / size_of::<T>()` values, without copying.
+    /// 
+    /// Fails to compile if a `T` is zero-sized, or more aligned than the byte-array, or if `SIZE`
+    /// is not a multiple of its size.
+    pub const fn view_slice_mut<T: FromBytes + AsBytes>(&mut self) -> &mut [T] {
+        const { Self::check_slice_of::<T>() };
+        
+        // SAFETY: As for `view_mut`, for each element.
+        unsafe { from_raw_parts_mut(self.bytes.as_mut_ptr() as *mut T, SIZE / size_of::<T>()) }
+    }
+    
+    /// Copies the byte-array, followed by `other`.
+    /// 
+    /// Fails to compile if `OUT` is not `SIZE + OTHER`.
+    pub const fn concat<
+        const OTHER: usize,
+        const OTHER_ALIGN: usize,
+        const OUT: usize
+    >(&self, other: &AlignedBytes<OTHER, OTHER_ALIGN>) -> AlignedBytes<OUT, ALIGN>
+        where Alignment<OTHER_ALIGN>: ValidAlignment
+    {
+        const { Self::check_sum_of_sizes(OUT, SIZE, OTHER) };
+        
+        let mut bytes = [0; OUT];
+        let (start, end) = bytes.split_at_mut(SIZE);
+        
+        start.copy_from_slice(&self.bytes);
+        end.copy_from_slice(&other.bytes);
+        
+        AlignedBytes::from(bytes)
+    }
+    
+    /// Copies the first `N` bytes, and the remaining `REST` bytes, of the byte-array (both aligned
+    /// to `ALIGN` bytes).
+    /// 
+    /// Fails to compile if `SIZE` is not `N + REST`.
+    pub const fn split_at<
+        const N: usize,
+        const REST: usize
+    >(&self) -> (AlignedBytes<N, ALIGN>, AlignedBytes<REST, ALIGN>) {
+        const { Self::check_sum_of_sizes(SIZE, N, REST) };
+        
+        let Some((start, end)) = self.bytes.split_first_chunk::<N>() else { unreachable!() };
+        let Some(end) = end.first_chunk::<REST>() else { unreachable!() };
+        
+        (AlignedBytes::from(*start), AlignedBytes::from(*end))
+    }
+    
+    /// Borrows the `LEN` bytes at `OFFSET` as a byte-array aligned to `SUB_ALIGN` bytes, without
+    /// copying.
+    /// 
+    /// Fails to compile if the bytes are out of bounds, or aren't known to be aligned to
+    /// `SUB_ALIGN` bytes (i.e. if `SUB_ALIGN` exceeds `ALIGN`, or `OFFSET` isn't a multiple of it).
+    pub const fn sub<
+        const OFFSET: usize,
+        const LEN: usize,
+        const SUB_ALIGN: usize
+    >(&self) -> &AlignedBytes<LEN, SUB_ALIGN>
+        where Alignment<SUB_ALIGN>: ValidAlignment
+    {
+        const { Self::check_sub::<OFFSET, LEN, SUB_ALIGN>() };
+        
+        // SAFETY: The bytes (and any padding of the sub-array) are within this byte-array, and
+        //         aligned to `SUB_ALIGN` bytes. Both byte-arrays are `repr(C)`, so the bytes are
+        //         at their start.
+        unsafe { &*(self.bytes.as_ptr().add(OFFSET) as *const AlignedBytes<LEN, SUB_ALIGN>) }
+    }
+    
+    /// Mutably borrows the `LEN` bytes at `OFFSET` as a byte-array aligned to `SUB_ALIGN` bytes,
+    /// without copying.
+    /// 
+    /// Fails to compile under the same conditions as [sub](AlignedBytes::sub), or if `LEN` isn't a
+    /// multiple of `SUB_ALIGN` (as writing the sub-array, including its trailing padding, would
+    /// de-initialise bytes of this byte-array):
+    /// ```compile_fail
+    /// use dynrsaur::align::AlignedBytes;
+    /// 
+    /// let mut bytes = AlignedBytes::<8, 4>::zeroed();
+    /// 
+    /// *bytes.sub_mut::<0, 3, 4>() = AlignedBytes::from([1, 2, 3]);
+    /// ```
+    pub const fn sub_mut<
+        const OFFSET: usize,
+        const LEN: usize,
+        const SUB_ALIGN: usize
+    >(&mut self) -> &mut AlignedBytes<LEN, SUB_ALIGN>
+        where Alignment<SUB_ALIGN>: ValidAlignment
+    {
+        const { Self::check_sub_mut::<OFFSET, LEN, SUB_ALIGN>() };
+        
+        // SAFETY: As for `sub`. The sub-array has no padding, so writes to it only write
+        //         initialised bytes.
+        unsafe { &mut *(self.bytes.as_mut_ptr().add(OFFSET) as *mut AlignedBytes<LEN, SUB_ALIGN>) }
+    }
+    
+    /// Reads (copies) a `T` from the bytes starting at `offset` (which needn't be aligned), or
+    /// returns [None] if the `T` would extend past the end of the byte-array.
+    pub const fn read_at<T: FromBytes>(&self, offset: usize) -> Option<T> {
+        match offset <= SIZE && size_of::<T>() <= SIZE - offset {
+            // SAFETY: The bytes are in bounds, and are a valid `T` (as any bytes are).
+            true => Some(unsafe { (self.bytes.as_ptr().add(offset) as *const T).read_unaligned() }),
+            false => None
+        }
+    }
+    
+    /// Panics if `count` `T`s are too big, or a `T` is too aligned, to be viewed in the
+    /// byte-array.
+    const fn check_size_and_align_of<T>(count: usize) {
+        if size_of::<T>() * count > SIZE {
+            // TODO: Add type_name::<T> once const-stable
+            concat_panic!(
+                "Size (",
+                size_of::<T>() * count,
+                " bytes) exceeds size of byte-array (",
+                SIZE,
+                " bytes)"
+            )
+        }
+        
+        if align_of::<T>() > ALIGN {
+            // TODO: Add type_name::<T> once const-stable
+            concat_panic!(
+                "Align (",
+                align_of::<T>(),
+                " bytes) exceeds align of byte-array (",
+                ALIGN,
+                " bytes)"
+            )
+        }
+    }
+    
+    /// Panics if `sum` is not `a + b`.
+    const fn check_sum_of_sizes(sum: usize, a: usize, b: usize) {
+        if a.checked_add(b).is_none() || sum != a + b {
+            concat_panic!(
+                "Size (",
+                sum,
+                " bytes) is not the sum of sizes (",
+                a,
+                " + ",
+                b,
+                " bytes)"
+            )
+        }
+    }
+    
+    /// Panics if the `LEN` bytes at `OFFSET` can't be borrowed with an alignment of `SUB_ALIGN`.
+    const fn check_sub<
+        const OFFSET: usize,
+        const LEN: usize,
+        const SUB_ALIGN: usize
+    >()
+        where Alignment<SUB_ALIGN>: ValidAlignment
+    {
+        // The sub-array may have trailing padding, which must also be in bounds
+        let size = size_of::<AlignedBytes<LEN, SUB_ALIGN>>();
+        
+        if OFFSET > SIZE || size > SIZE - OFFSET {
+            concat_panic!(
+                "Sub-array (",
+                size,
+                " bytes at offset ",
+                OFFSET,
+                ") exceeds size of byte-array (",
+                SIZE,
+                " bytes)"
+            )
+        }
+        
+        if SUB_ALIGN > ALIGN || !OFFSET.is_multiple_of(SUB_ALIGN) {
+            concat_panic!(
+                "Sub-array at offset ",
+                OFFSET,
+                " of byte-array (aligned to ",
+                ALIGN,
+                " bytes) is not aligned to ",
+                SUB_ALIGN,
+                " bytes"
+            )
+        }
+    }
+    
+    /// Panics if the `LEN` bytes at `OFFSET` can't be mutably borrowed with an alignment of
+    /// `SUB_ALIGN`.
+    const fn check_sub_mut<
+        const OFFSET: usize,
+        const LEN: usize,
+        const SUB_ALIGN: usize
+    >()
+        where Alignment<SUB_ALIGN>: ValidAlignment
+    {
+        Self::check_sub::<OFFSET, LEN, SUB_ALIGN>();
+        
+        if !LEN.is_multiple_of(SUB_ALIGN) {
+            concat_panic!(
+                "Mutable sub-array (",
+                LEN,
+                " bytes) is padded, as its size is not a multiple of its alignment (",
+                SUB_ALIGN,
+                " bytes)"
+            )
+        }
+    }
+    
+    /// Panics if the byte-array can't be viewed as a slice of `T`s.
+    const fn check_slice_of<T>() {
+        if size_of::<T>() == 0 || !SIZE.is_multiple_of(size_of::<T>()) {
+            // TODO: Add type_name::<T> once const-stable
+            concat_panic!(
+                "Size of byte-array (",
+                SIZE,
+                " bytes) is not a multiple of size (",
+                size_of::<T>(),
+                " bytes)"
+            )
+        }
+        
+        Self::check_size_and_align_of::<T>(SIZE / size_of::<T>())
+    }
 }
 
 impl<
@@ -99,4 +374,66 @@ impl<
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.bytes
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{AlignedBytes, AsBytes};
+
+    #[test]
+    fn views() {
+        let mut bytes = AlignedBytes::<16, 8>::zeroed();
+
+        *bytes.view_mut::<u64>() = u64::MAX;
+        bytes.view_slice_mut::<u32>()[3] = 7;
+
+        assert_eq!(*bytes.view::<u64>(), u64::MAX);
+        assert_eq!(bytes.view_slice::<u32>(), &[u32::MAX, u32::MAX, 0, 7]);
+        assert_eq!(bytes.view::<[u16; 4]>().as_bytes(), &[0xFF; 8]);
+    }
+
+    #[test]
+    fn composition() {
+        static FIRST: AlignedBytes<4, 8> = AlignedBytes::from([1, 2, 3, 4]);
+        static BOTH: AlignedBytes<12, 8> = FIRST.concat(&AlignedBytes::<8, 1>::from([5; 8]));
+        static SUB: &AlignedBytes<8, 4> = BOTH.sub::<4, 8, 4>();
+        const SPLIT: (AlignedBytes<2, 8>, AlignedBytes<10, 8>) = BOTH.split_at();
+
+        assert_eq!(BOTH.as_bytes(), &[1, 2, 3, 4, 5, 5, 5, 5, 5, 5, 5, 5]);
+        assert_eq!(SUB.as_bytes(), &[5; 8]);
+        assert_eq!(SUB.as_ptr() as usize % 4, 0);
+        assert_eq!(SPLIT.0.as_bytes(), &[1, 2]);
+        assert_eq!(SPLIT.1.as_bytes(), &[3, 4, 5, 5, 5, 5, 5, 5, 5, 5]);
+
+        let mut bytes = AlignedBytes::<8, 8>::zeroed();
+        *bytes.sub_mut::<6, 2, 2>() = AlignedBytes::from([6, 7]);
+
+        assert_eq!(bytes.as_bytes(), &[0, 0, 0, 0, 0, 0, 6, 7]);
+    }
+
+    #[test]
+    fn padded_sub() {
+        let mut bytes = AlignedBytes::<8, 4>::from([1, 2, 3, 4, 5, 6, 7, 8]);
+
+        // The trailing padding of the sub-array overlaps the 4th byte
+        let sub = bytes.sub::<0, 3, 4>();
+
+        assert_eq!(size_of_val(sub), 4);
+        assert_eq!(sub.as_bytes(), &[1, 2, 3]);
+
+        // Mutable sub-arrays must be unpadded
+        *bytes.sub_mut::<4, 4, 4>() = AlignedBytes::from([0; 4]);
+
+        assert_eq!(bytes.as_bytes(), &[1, 2, 3, 4, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn read_at() {
+        let bytes = AlignedBytes::<8, 1>::from([0, 1, 2, 3, 4, 5, 6, 7]);
+
+        assert_eq!(bytes.read_at::<[u8; 2]>(3), Some([3, 4]));
+        assert_eq!(bytes.read_at::<u32>(4), Some(u32::from_ne_bytes([4, 5, 6, 7])));
+        assert_eq!(bytes.read_at::<u32>(5), None);
+        assert_eq!(bytes.read_at::<u8>(usize::MAX), None);
+    }
+}
@@ -0,0 +1,149 @@
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use crate::align::EndianBytes;
+
+/// A cursor over an aligned buffer (see [EndianBytes::cursor]), for reading/writing it via
+/// [Read]/[Write] and [Seek].
+///
+/// Like a [Cursor](std::io::Cursor) over `&mut [u8]`, writes overwrite the buffer in place (the
+/// buffer doesn't grow), and stop at its end.
+///
+/// Generic Parameters:
+/// - `B`: the type of the buffer.
+pub struct AlignedCursor<'buffer, B: EndianBytes + ?Sized> {
+    /// The buffer.
+    buffer: &'buffer mut B,
+    /// The offset of the next read/write into the buffer.
+    position: u64
+}
+
+impl<'buffer, B: EndianBytes + ?Sized> AlignedCursor<'buffer, B> {
+    /// Creates a cursor at the start of the `buffer`.
+    pub fn new(buffer: &'buffer mut B) -> Self {
+        AlignedCursor {
+            buffer,
+            position: 0
+        }
+    }
+
+    /// The offset of the next read/write into the buffer.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Moves the cursor to `position` (which may be past the end of the buffer).
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position
+    }
+
+    /// Borrows the buffer.
+    pub fn get_ref(&self) -> &B {
+        self.buffer
+    }
+
+    /// Mutably borrows the buffer.
+    pub fn get_mut(&mut self) -> &mut B {
+        self.buffer
+    }
+
+    /// Returns the borrow of the buffer.
+    pub fn into_inner(self) -> &'buffer mut B {
+        self.buffer
+    }
+
+    /// The position of the cursor in the buffer (clamped to its end).
+    fn offset(&self) -> usize {
+        let len = self.buffer.as_byte_slice().len();
+
+        usize::try_from(self.position).map_or(len, |position| position.min(len))
+    }
+}
+
+impl<B: EndianBytes + ?Sized> Read for AlignedCursor<'_, B> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let offset = self.offset();
+        let remaining = &self.buffer.as_byte_slice()[offset..];
+        let len = remaining.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&remaining[..len]);
+        self.position += len as u64;
+
+        Ok(len)
+    }
+}
+
+impl<B: EndianBytes + ?Sized> Write for AlignedCursor<'_, B> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let offset = self.offset();
+        let remaining = &mut self.buffer.as_byte_slice_mut()[offset..];
+        let len = remaining.len().min(buf.len());
+
+        remaining[..len].copy_from_slice(&buf[..len]);
+        self.position += len as u64;
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<B: EndianBytes + ?Sized> Seek for AlignedCursor<'_, B> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let (base, delta) = match pos {
+            SeekFrom::Start(position) => {
+                self.position = position;
+
+                return Ok(position)
+            },
+            SeekFrom::End(delta) => (self.buffer.as_byte_slice().len() as u64, delta),
+            SeekFrom::Current(delta) => (self.position, delta)
+        };
+
+        match base.checked_add_signed(delta) {
+            Some(position) => {
+                self.position = position;
+
+                Ok(position)
+            },
+            None => Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position"
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Seek, SeekFrom, Write};
+    use crate::align::{AlignedBytes, EndianBytes};
+
+    #[test]
+    fn read_write_seek() {
+        let mut bytes = AlignedBytes::<8, 8>::zeroed();
+        let mut cursor = bytes.cursor();
+
+        cursor.write_all(&[1, 2, 3]).unwrap();
+        cursor.write_all(&0x0405u16.to_be_bytes()).unwrap();
+
+        assert_eq!(cursor.write(&[6, 7, 8, 9]).unwrap(), 3);
+        assert_eq!(cursor.write(&[10]).unwrap(), 0);
+
+        assert_eq!(cursor.seek(SeekFrom::End(-5)).unwrap(), 3);
+        assert_eq!(cursor.get_ref().u16_be_at(3), Some(0x0405));
+        assert!(cursor.seek(SeekFrom::Current(-4)).is_err());
+
+        let mut read = [0; 4];
+        cursor.read_exact(&mut read).unwrap();
+
+        assert_eq!(read, [4, 5, 6, 7]);
+        assert_eq!(cursor.read(&mut read).unwrap(), 1);
+        assert_eq!(read[0], 8);
+
+        cursor.set_position(100);
+
+        assert_eq!(cursor.read(&mut read).unwrap(), 0);
+        assert_eq!(bytes.as_bytes(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+}
@@ -0,0 +1,135 @@
+use std::mem::MaybeUninit;
+use crate::align::{AlignedBytes, Alignment};
+use crate::align::valid_alignment::ValidAlignment;
+
+/// Possibly-uninitialised storage of `SIZE` bytes, aligned to `ALIGN` bytes.
+/// 
+/// The uninitialised counterpart of [AlignedBytes], for storing values of other types in-place
+/// without first zeroing the storage. As such values may contain uninitialised (padding) bytes,
+/// the content can only be borrowed as bytes where it is [known to be
+/// initialised](AlignedUninit::assume_init_ref).
+pub struct AlignedUninit<
+    const SIZE: usize,
+    const ALIGN: usize
+>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    _align: Alignment<ALIGN>,
+    bytes: [MaybeUninit<u8>; SIZE]
+}
+
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> AlignedUninit<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Creates uninitialised aligned storage.
+    pub const fn uninit() -> Self {
+        AlignedUninit {
+            _align: Alignment::new(),
+            bytes: [MaybeUninit::uninit(); SIZE]
+        }
+    }
+    
+    /// Creates aligned storage, where all bytes are (initialised to) zero.
+    pub const fn zeroed() -> Self {
+        AlignedUninit {
+            _align: Alignment::new(),
+            bytes: [MaybeUninit::new(0); SIZE]
+        }
+    }
+    
+    /// Returns a pointer to the start of the storage.
+    pub const fn as_ptr(&self) -> *const u8 {
+        &self.bytes as *const [MaybeUninit<u8>; SIZE] as *const u8
+    }
+    
+    /// Returns a mutable pointer to the start of the storage.
+    pub const fn as_mut_ptr(&mut self) -> *mut u8 {
+        &mut self.bytes as *mut [MaybeUninit<u8>; SIZE] as *mut u8
+    }
+    
+    /// Borrows the (possibly uninitialised) bytes of the storage.
+    pub const fn as_uninit_bytes(&self) -> &[MaybeUninit<u8>; SIZE] {
+        &self.bytes
+    }
+    
+    /// Mutably borrows the (possibly uninitialised) bytes of the storage.
+    pub const fn as_uninit_bytes_mut(&mut self) -> &mut [MaybeUninit<u8>; SIZE] {
+        &mut self.bytes
+    }
+    
+    /// Borrows the bytes of the storage.
+    /// 
+    /// # Safety
+    /// 
+    /// All bytes must be initialised (e.g. the storage must not hold a value with padding).
+    pub const unsafe fn assume_init_ref(&self) -> &[u8; SIZE] {
+        &*(self.as_ptr() as *const [u8; SIZE])
+    }
+    
+    /// Mutably borrows the bytes of the storage.
+    /// 
+    /// # Safety
+    /// 
+    /// All bytes must be initialised (e.g. the storage must not hold a value with padding).
+    pub const unsafe fn assume_init_mut(&mut self) -> &mut [u8; SIZE] {
+        &mut *(self.as_mut_ptr() as *mut [u8; SIZE])
+    }
+    
+    /// Converts to the initialised bytes of the storage.
+    /// 
+    /// # Safety
+    /// 
+    /// All bytes must be initialised (e.g. the storage must not hold a value with padding).
+    pub const unsafe fn assume_init(self) -> AlignedBytes<SIZE, ALIGN> {
+        AlignedBytes::from(*self.assume_init_ref())
+    }
+}
+
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> Default for AlignedUninit<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn default() -> Self {
+        Self::uninit()
+    }
+}
+
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> From<AlignedBytes<SIZE, ALIGN>> for AlignedUninit<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(value: AlignedBytes<SIZE, ALIGN>) -> Self {
+        AlignedUninit {
+            _align: Alignment::new(),
+            bytes: value.as_bytes().map(MaybeUninit::new)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{AlignedBytes, AlignedUninit};
+
+    #[test]
+    fn aligned() {
+        let storage = AlignedUninit::<3, 64>::uninit();
+        
+        assert_eq!(storage.as_ptr() as usize % 64, 0);
+        assert_eq!(size_of::<AlignedUninit<3, 64>>(), 64);
+    }
+
+    #[test]
+    fn from_initialised_bytes() {
+        let storage = AlignedUninit::from(AlignedBytes::<4, 4>::from([1, 2, 3, 4]));
+        
+        assert_eq!(unsafe { storage.assume_init_ref() }, &[1, 2, 3, 4]);
+        assert_eq!(*unsafe { AlignedUninit::<4, 4>::zeroed().assume_init() }, [0; 4]);
+    }
+}
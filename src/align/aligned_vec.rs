@@ -0,0 +1,492 @@
+use std::alloc::{alloc, alloc_zeroed, dealloc, handle_alloc_error, realloc, Layout};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::ptr::{copy_nonoverlapping, without_provenance_mut, NonNull};
+use crate::align::{AlignedBox, AlignedBytes, Alignment};
+use crate::align::valid_alignment::ValidAlignment;
+
+/// A growable byte-buffer on the heap, whose start is aligned to `ALIGN` bytes.
+///
+/// The heap counterpart of [AlignedBytes], e.g. for block IO or SIMD buffers. Like `Vec<u8>`, no
+/// memory is allocated until the buffer has a non-zero capacity.
+///
+/// Generic Parameters:
+/// - `ALIGN`: the alignment of the buffer. Must be a [valid alignment](ValidAlignment).
+pub struct AlignedVec<const ALIGN: usize>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Pointer to the buffer (dangling, but aligned, if the capacity is zero).
+    ptr: NonNull<u8>,
+    /// The number of initialised bytes in the buffer.
+    len: usize,
+    /// The size of the buffer.
+    capacity: usize,
+    /// Marker for the alignment of the buffer (a [PhantomData], as the `AlignedVec` itself
+    /// needn't be aligned).
+    _align: PhantomData<Alignment<ALIGN>>
+}
+
+impl<const ALIGN: usize> AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Creates an empty buffer, without allocating.
+    pub const fn new() -> Self {
+        AlignedVec {
+            ptr: dangling::<ALIGN>(),
+            len: 0,
+            capacity: 0,
+            _align: PhantomData
+        }
+    }
+
+    /// Creates an empty buffer which can hold at least `capacity` bytes without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec = Self::new();
+
+        vec.reserve_exact(capacity);
+
+        vec
+    }
+
+    /// Creates a buffer of `len` zero bytes.
+    pub fn zeroed(len: usize) -> Self {
+        AlignedVec {
+            // SAFETY: `alloc_zeroed` initialises the buffer.
+            ptr: unsafe { allocate::<ALIGN>(len, alloc_zeroed) },
+            len,
+            capacity: len,
+            _align: PhantomData
+        }
+    }
+
+    /// The number of bytes in the buffer.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer contains no bytes.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of bytes the buffer can hold without reallocating.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns an (aligned) pointer to the start of the buffer.
+    pub const fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns an (aligned) mutable pointer to the start of the buffer.
+    pub const fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Borrows the bytes of the buffer.
+    pub const fn as_slice(&self) -> &[u8] {
+        // SAFETY: The first `len` bytes are initialised.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Mutably borrows the bytes of the buffer.
+    pub const fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: The first `len` bytes are initialised, and uniquely borrowed.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Reserves capacity for at least `additional` more bytes, growing the buffer geometrically
+    /// (as `Vec` does) to amortise the cost of reallocation.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.required_capacity(additional);
+
+        if required > self.capacity {
+            // As for `Vec<u8>`, the smallest non-zero capacity is 8
+            self.grow_to(required.max(self.capacity.saturating_mul(2)).max(8));
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more bytes (if the buffer can't already hold
+    /// them).
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let required = self.required_capacity(additional);
+
+        if required > self.capacity {
+            self.grow_to(required);
+        }
+    }
+
+    /// Appends a byte to the end of the buffer.
+    pub fn push(&mut self, byte: u8) {
+        if self.len == self.capacity {
+            self.reserve(1);
+        }
+
+        // SAFETY: There is capacity for the byte.
+        unsafe { self.ptr.as_ptr().add(self.len).write(byte) }
+
+        self.len += 1;
+    }
+
+    /// Appends the bytes of `slice` to the end of the buffer.
+    pub fn extend_from_slice(&mut self, slice: &[u8]) {
+        self.reserve(slice.len());
+
+        // SAFETY: There is capacity for the bytes, and a `&mut self` can't overlap a `&[u8]`.
+        unsafe { copy_nonoverlapping(slice.as_ptr(), self.ptr.as_ptr().add(self.len), slice.len()) }
+
+        self.len += slice.len();
+    }
+
+    /// Shortens the buffer to `len` bytes (keeping its capacity). Does nothing if the buffer is
+    /// already no longer than `len`.
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    /// Removes all bytes from the buffer (keeping its capacity).
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Converts to a boxed slice, shrinking the allocation to the length of the buffer.
+    pub fn into_boxed_slice(mut self) -> AlignedBox<[u8], ALIGN> {
+        self.grow_to(self.len);
+
+        let vec = ManuallyDrop::new(self);
+
+        // SAFETY: The allocation (if any) is exactly `len` bytes, and is owned by the box.
+        unsafe { AlignedBox::from_raw_parts(vec.ptr, vec.len) }
+    }
+
+    /// Takes ownership of the buffer of `capacity` bytes at `ptr`, the first `len` of which are
+    /// initialised.
+    ///
+    /// SAFETY: `ptr` must have been [allocated](allocate) with `capacity` bytes.
+    pub(super) unsafe fn from_raw_parts(ptr: NonNull<u8>, len: usize, capacity: usize) -> Self {
+        AlignedVec {
+            ptr,
+            len,
+            capacity,
+            _align: PhantomData
+        }
+    }
+
+    /// The capacity needed to hold `additional` more bytes.
+    fn required_capacity(&self, additional: usize) -> usize {
+        self.len.checked_add(additional).expect("capacity overflow")
+    }
+
+    /// Reallocates the buffer to exactly `capacity` bytes (which must be at least `len`).
+    fn grow_to(&mut self, capacity: usize) {
+        debug_assert!(capacity >= self.len);
+
+        if capacity == self.capacity {
+            return;
+        }
+
+        // SAFETY: The buffer was allocated with the layout for its capacity.
+        self.ptr = unsafe { reallocate::<ALIGN>(self.ptr, self.capacity, capacity) };
+        self.capacity = capacity;
+    }
+}
+
+impl<const ALIGN: usize> Drop for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn drop(&mut self) {
+        // SAFETY: The buffer was allocated with the layout for its capacity.
+        unsafe { deallocate::<ALIGN>(self.ptr, self.capacity) }
+    }
+}
+
+// SAFETY: The buffer is uniquely owned bytes, like `Vec<u8>`.
+unsafe impl<const ALIGN: usize> Send for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+unsafe impl<const ALIGN: usize> Sync for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+impl<const ALIGN: usize> Deref for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> DerefMut for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<const ALIGN: usize> Default for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const ALIGN: usize> Clone for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn clone(&self) -> Self {
+        Self::from(self.as_slice())
+    }
+}
+
+impl<const ALIGN: usize> Debug for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<const ALIGN: usize> PartialEq for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> Eq for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+impl<const ALIGN: usize> Extend<u8> for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+
+        self.reserve(iter.size_hint().0);
+
+        iter.for_each(|byte| self.push(byte));
+    }
+}
+
+impl<'a, const ALIGN: usize> Extend<&'a u8> for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<const ALIGN: usize> FromIterator<u8> for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut vec = Self::new();
+
+        vec.extend(iter);
+
+        vec
+    }
+}
+
+impl<const ALIGN: usize> From<&[u8]> for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(slice: &[u8]) -> Self {
+        let mut vec = Self::with_capacity(slice.len());
+
+        vec.extend_from_slice(slice);
+
+        vec
+    }
+}
+
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> From<AlignedBytes<SIZE, ALIGN>> for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(bytes: AlignedBytes<SIZE, ALIGN>) -> Self {
+        Self::from(bytes.as_bytes().as_slice())
+    }
+}
+
+impl<const ALIGN: usize> From<AlignedBox<[u8], ALIGN>> for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn from(boxed: AlignedBox<[u8], ALIGN>) -> Self {
+        boxed.into_vec()
+    }
+}
+
+/// Returns the vector if its length is not `SIZE`.
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> TryFrom<AlignedVec<ALIGN>> for AlignedBytes<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    type Error = AlignedVec<ALIGN>;
+
+    fn try_from(vec: AlignedVec<ALIGN>) -> Result<Self, Self::Error> {
+        match <[u8; SIZE]>::try_from(vec.as_slice()) {
+            Ok(bytes) => Ok(AlignedBytes::from(bytes)),
+            Err(_) => Err(vec)
+        }
+    }
+}
+
+/// The layout of an `ALIGN`-aligned buffer of `size` bytes, derived from the [alignment
+/// marker](Alignment).
+pub(super) fn buffer_layout<const ALIGN: usize>(size: usize) -> Layout
+    where Alignment<ALIGN>: ValidAlignment
+{
+    Layout::from_size_align(size, align_of::<Alignment<ALIGN>>()).expect("capacity overflow")
+}
+
+/// A dangling, but `ALIGN`-aligned, pointer for empty buffers.
+pub(super) const fn dangling<const ALIGN: usize>() -> NonNull<u8>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    // SAFETY: Alignments are non-zero.
+    unsafe { NonNull::new_unchecked(without_provenance_mut(align_of::<Alignment<ALIGN>>())) }
+}
+
+/// Allocates an `ALIGN`-aligned buffer of `size` bytes with `allocator` (either [alloc] or
+/// [alloc_zeroed]), aborting if the allocation fails.
+///
+/// SAFETY: The buffer is only initialised if allocated with [alloc_zeroed].
+pub(super) unsafe fn allocate<const ALIGN: usize>(
+    size: usize,
+    allocator: unsafe fn(Layout) -> *mut u8
+) -> NonNull<u8>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    if size == 0 {
+        return dangling::<ALIGN>();
+    }
+
+    let layout = buffer_layout::<ALIGN>(size);
+
+    NonNull::new(allocator(layout)).unwrap_or_else(|| handle_alloc_error(layout))
+}
+
+/// Reallocates an `ALIGN`-aligned buffer from `old_size` to `new_size` bytes, aborting if the
+/// allocation fails.
+///
+/// SAFETY: `ptr` must have been [allocated](allocate) with `old_size` bytes.
+unsafe fn reallocate<const ALIGN: usize>(
+    ptr: NonNull<u8>,
+    old_size: usize,
+    new_size: usize
+) -> NonNull<u8>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    match (old_size, new_size) {
+        (0, _) => allocate::<ALIGN>(new_size, alloc),
+        (_, 0) => {
+            deallocate::<ALIGN>(ptr, old_size);
+
+            dangling::<ALIGN>()
+        },
+        _ => {
+            let new_layout = buffer_layout::<ALIGN>(new_size);
+
+            NonNull::new(realloc(ptr.as_ptr(), buffer_layout::<ALIGN>(old_size), new_size))
+                .unwrap_or_else(|| handle_alloc_error(new_layout))
+        }
+    }
+}
+
+/// Frees an `ALIGN`-aligned buffer of `size` bytes.
+///
+/// SAFETY: `ptr` must have been [allocated](allocate) with `size` bytes.
+pub(super) unsafe fn deallocate<const ALIGN: usize>(ptr: NonNull<u8>, size: usize)
+    where Alignment<ALIGN>: ValidAlignment
+{
+    if size > 0 {
+        dealloc(ptr.as_ptr(), buffer_layout::<ALIGN>(size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{AlignedBytes, AlignedVec};
+
+    #[test]
+    fn aligned() {
+        let mut vec = AlignedVec::<4096>::new();
+
+        assert_eq!(vec.as_ptr() as usize % 4096, 0);
+
+        for byte in 0..=255u8 {
+            vec.push(byte);
+
+            assert_eq!(vec.as_ptr() as usize % 4096, 0);
+        }
+
+        vec.extend_from_slice(&[1; 5000]);
+
+        assert_eq!(vec.as_ptr() as usize % 4096, 0);
+        assert_eq!(vec.len(), 5256);
+        assert_eq!(vec[255], 255);
+        assert_eq!(vec[5255], 1);
+    }
+
+    #[test]
+    fn reserve_and_truncate() {
+        let mut vec = AlignedVec::<64>::with_capacity(10);
+
+        assert_eq!(vec.capacity(), 10);
+
+        vec.extend([1u8, 2, 3].iter());
+        vec.reserve(100);
+
+        assert!(vec.capacity() >= 103);
+
+        vec.truncate(1);
+
+        assert_eq!(&*vec, [1]);
+
+        vec.clear();
+
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn bytes_conversions() {
+        let vec = AlignedVec::from(AlignedBytes::<4, 16>::from([1, 2, 3, 4]));
+
+        assert_eq!(&*vec, [1, 2, 3, 4]);
+
+        let Err(vec) = AlignedBytes::<3, 16>::try_from(vec) else { panic!("wrong length") };
+        let Ok(bytes) = AlignedBytes::<4, 16>::try_from(vec) else { panic!("right length") };
+
+        assert_eq!(bytes.as_bytes(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let vec: AlignedVec<32> = (0..10).collect();
+
+        let boxed = vec.into_boxed_slice();
+
+        assert_eq!(boxed.as_ptr() as usize % 32, 0);
+        assert_eq!(&*boxed, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let mut vec = boxed.into_vec();
+        vec.push(10);
+
+        assert_eq!(vec.len(), 11);
+        assert_eq!(AlignedVec::<32>::new().into_boxed_slice().len(), 0);
+    }
+}
@@ -0,0 +1,173 @@
+//! Type-level arithmetic on [const-generic alignments](Alignment).
+//!
+//! Stable Rust can't compute const-generic arguments from other const-generic parameters (i.e.
+//! `generic_const_exprs`), so these are implemented for every pair of alignments instead.
+
+use sealed::sealed;
+use crate::align::Alignment;
+use crate::align::valid_alignment::ValidAlignment;
+
+/// The larger of two [alignments](Alignment), e.g. for a buffer holding values of both.
+///
+/// Sealed trait that is implemented for every pair of alignments (see [Max]).
+///
+/// Generic Parameters:
+/// - `Other`: the alignment to compare with.
+#[sealed(pub(in super))]
+pub trait MaxWith<Other: ValidAlignment>: ValidAlignment {
+    /// The larger of the alignments.
+    type Output: ValidAlignment;
+}
+
+/// The smaller of two [alignments](Alignment).
+///
+/// Sealed trait that is implemented for every pair of alignments (see [Min]).
+///
+/// Generic Parameters:
+/// - `Other`: the alignment to compare with.
+#[sealed(pub(in super))]
+pub trait MinWith<Other: ValidAlignment>: ValidAlignment {
+    /// The smaller of the alignments.
+    type Output: ValidAlignment;
+}
+
+/// Indicates that the [alignment](Alignment) is at least `Other`, e.g. for a bound that `ALIGN`
+/// is at least 16 bytes:
+/// ```
+/// use dynrsaur::align::{AlignedBytes, Alignment, AtLeast, ValidAlignment};
+///
+/// fn simd_lanes<const ALIGN: usize>(bytes: &AlignedBytes<64, ALIGN>) -> usize
+///     where Alignment<ALIGN>: ValidAlignment + AtLeast<Alignment<16>>
+/// {
+///     bytes.len() / 16
+/// }
+///
+/// assert_eq!(simd_lanes(&AlignedBytes::<64, 32>::zeroed()), 4);
+/// ```
+///
+/// Generic Parameters:
+/// - `Other`: the minimum alignment.
+#[sealed(pub(in super))]
+pub trait AtLeast<Other: ValidAlignment>: ValidAlignment {}
+
+/// Indicates that the [alignment](Alignment) is at most `Other`.
+///
+/// Generic Parameters:
+/// - `Other`: the maximum alignment.
+#[sealed(pub(in super))]
+pub trait AtMost<Other: ValidAlignment>: ValidAlignment {}
+
+/// The larger of the [alignments](Alignment) `A` and `B`.
+pub type Max<A, B> = <A as MaxWith<B>>::Output;
+
+/// The smaller of the [alignments](Alignment) `A` and `B`.
+pub type Min<A, B> = <A as MinWith<B>>::Output;
+
+/// The [const-generic alignment](Alignment) of an [alignment marker](crate::align::align_markers),
+/// for use with the operations above.
+pub type AlignOfMarker<M> = <M as ValidAlignment>::Alignment;
+
+/// Implements the operations for every pair of alignments (given in ascending order).
+macro_rules! impl_alignment_ops {
+    ($($aligns:literal)*) => {
+        impl_alignment_ops!(@ascending [] $($aligns)*);
+    };
+    (@ascending [$($smaller:literal)*]) => {};
+    (@ascending [$($smaller:literal)*] $align:literal $($larger:literal)*) => {
+        $(
+            impl_alignment_ops!(@pair $align, $smaller);
+        )*
+
+        $(
+            #[sealed]
+            impl AtLeast<Alignment<$smaller>> for Alignment<$align> {}
+
+            #[sealed]
+            impl AtMost<Alignment<$align>> for Alignment<$smaller> {}
+        )*
+
+        #[sealed]
+        impl MaxWith<Alignment<$align>> for Alignment<$align> {
+            type Output = Alignment<$align>;
+        }
+
+        #[sealed]
+        impl MinWith<Alignment<$align>> for Alignment<$align> {
+            type Output = Alignment<$align>;
+        }
+
+        #[sealed]
+        impl AtLeast<Alignment<$align>> for Alignment<$align> {}
+
+        #[sealed]
+        impl AtMost<Alignment<$align>> for Alignment<$align> {}
+
+        impl_alignment_ops!(@ascending [$($smaller)* $align] $($larger)*);
+    };
+    (@pair $larger:literal, $smaller:literal) => {
+        #[sealed]
+        impl MaxWith<Alignment<$smaller>> for Alignment<$larger> {
+            type Output = Alignment<$larger>;
+        }
+
+        #[sealed]
+        impl MaxWith<Alignment<$larger>> for Alignment<$smaller> {
+            type Output = Alignment<$larger>;
+        }
+
+        #[sealed]
+        impl MinWith<Alignment<$smaller>> for Alignment<$larger> {
+            type Output = Alignment<$smaller>;
+        }
+
+        #[sealed]
+        impl MinWith<Alignment<$larger>> for Alignment<$smaller> {
+            type Output = Alignment<$smaller>;
+        }
+    };
+}
+
+#[cfg(target_pointer_width = "16")]
+impl_alignment_ops!(1 2 4 8 16 32 64 128 256 512 1024 2048 4096 8192 16384);
+
+#[cfg(not(target_pointer_width = "16"))]
+impl_alignment_ops!(
+    1 2 4 8 16 32 64 128 256 512 1024 2048 4096 8192 16384 32768 65536 131072 262144 524288
+    1048576 2097152 4194304 8388608 16777216 33554432 67108864 134217728 268435456 536870912
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{AlignOfMarker, Alignment, AtLeast, AtMost, Max, MaxWith, Min, ValidAlignment};
+    use crate::align::align_markers::AlignMarker32;
+
+    #[test]
+    fn max_and_min() {
+        assert_eq!(<Max<Alignment<8>, Alignment<64>> as ValidAlignment>::VALUE, 64);
+        assert_eq!(<Max<Alignment<64>, Alignment<8>> as ValidAlignment>::VALUE, 64);
+        assert_eq!(<Min<Alignment<8>, Alignment<64>> as ValidAlignment>::VALUE, 8);
+        assert_eq!(<Min<Alignment<4>, Alignment<4>> as ValidAlignment>::VALUE, 4);
+        assert_eq!(align_of::<Max<AlignOfMarker<AlignMarker32>, Alignment<1>>>(), 32);
+    }
+
+    #[test]
+    fn generic_bounds() {
+        fn combined<const A: usize, const B: usize>() -> usize
+            where Alignment<A>: ValidAlignment + MaxWith<Alignment<B>>,
+                  Alignment<B>: ValidAlignment
+        {
+            <Max<Alignment<A>, Alignment<B>> as ValidAlignment>::VALUE
+        }
+
+        fn bounded<const ALIGN: usize>() -> usize
+            where Alignment<ALIGN>: ValidAlignment + AtLeast<Alignment<16>> + AtMost<Alignment<4096>>
+        {
+            ALIGN
+        }
+
+        assert_eq!(combined::<16, 512>(), 512);
+        assert_eq!(combined::<16384, 1>(), 16384);
+        assert_eq!(bounded::<16>(), 16);
+        assert_eq!(bounded::<4096>(), 4096);
+    }
+}
@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+use std::slice::{from_raw_parts, from_raw_parts_mut};
+use crate::align::{Alignment, FromBytes, Immutable};
+use crate::align::valid_alignment::ValidAlignment;
+
+/// Indicates that all bytes of a value of the type are initialised (i.e. it has no padding), so
+/// it can be viewed as bytes.
+///
+/// Implemented for the primitive types, and arrays of `AsBytes` types. For structs, see
+/// [derive_bytes](crate::derive_bytes).
+///
+/// # Safety
+///
+/// The type must not contain padding (or other possibly-uninitialised bytes, e.g. in a
+/// [MaybeUninit](std::mem::MaybeUninit)).
+pub unsafe trait AsBytes {
+    /// Borrows the bytes of the value.
+    fn as_bytes(&self) -> &[u8]
+        where Self: Immutable
+    {
+        // SAFETY: All bytes are initialised, and can't be mutated while borrowed (as the value
+        //         is immutable).
+        unsafe { from_raw_parts(self as *const Self as *const u8, size_of_val(self)) }
+    }
+
+    /// Mutably borrows the bytes of the value.
+    fn as_bytes_mut(&mut self) -> &mut [u8]
+        where Self: FromBytes
+    {
+        // SAFETY: All bytes are initialised, and any bytes written are a valid value.
+        unsafe { from_raw_parts_mut(self as *mut Self as *mut u8, size_of_val(self)) }
+    }
+}
+
+/// Implements [AsBytes] for the given types.
+macro_rules! impl_as_bytes {
+    ($($types:ty),*) => {
+        $(
+            // SAFETY: Primitive types have no padding.
+            unsafe impl AsBytes for $types {}
+        )*
+    };
+}
+
+impl_as_bytes!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, ()
+);
+
+// SAFETY: Array elements have no padding between them (their size is a multiple of their
+//         alignment).
+unsafe impl<T: AsBytes, const N: usize> AsBytes for [T; N] {}
+unsafe impl<T: AsBytes> AsBytes for [T] {}
+
+// SAFETY: ZSTs have no bytes.
+unsafe impl<T: ?Sized> AsBytes for PhantomData<T> {}
+unsafe impl<const ALIGN: usize> AsBytes for Alignment<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
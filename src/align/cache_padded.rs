@@ -0,0 +1,142 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use crate::align::align_markers::CacheLineAlignment;
+use crate::align::valid_alignment::ValidAlignment;
+
+/// A value of any type `T` that is aligned (and padded) to a
+/// [cache-line](crate::align::align_markers::CacheLineAlignment), so that values which are written
+/// concurrently (e.g. per-thread counters) don't share a cache-line.
+///
+/// The counterpart of [Aligned](crate::align::Aligned), with an alignment chosen by the target
+/// architecture. For arrays of cache-padded values, see
+/// [CachePaddedArray](crate::align::CachePaddedArray).
+///
+/// Generic Parameters:
+/// - `T`: the type of the padded value.
+#[repr(C)]
+pub struct CachePadded<T> {
+    value: T,
+    _align: CacheLineAlignment
+}
+
+impl<T> CachePadded<T> {
+    /// Pads the given value.
+    pub const fn new(value: T) -> Self {
+        CachePadded {
+            value,
+            _align: CacheLineAlignment::MARKER
+        }
+    }
+
+    /// Returns the padded value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Borrows the padded value.
+    pub const fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Mutably borrows the padded value.
+    pub const fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T> From<T> for CachePadded<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+// The traits below are implemented manually (rather than derived), as the derives would require
+// them of the alignment marker too.
+
+impl<T: Clone> Clone for CachePadded<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T: Copy> Copy for CachePadded<T> {}
+
+impl<T: Default> Default for CachePadded<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Debug> Debug for CachePadded<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CachePadded")
+            .field(&self.value)
+            .finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for CachePadded<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for CachePadded<T> {}
+
+impl<T: PartialOrd> PartialOrd for CachePadded<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord> Ord for CachePadded<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: Hash> Hash for CachePadded<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::align_markers::CacheLineAlignment;
+    use crate::align::{CachePadded, ValidAlignment};
+
+    #[test]
+    fn padded() {
+        let line = CacheLineAlignment::VALUE;
+
+        #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+        assert_eq!(line, 128);
+
+        assert_eq!(align_of::<CachePadded<u8>>(), line);
+        assert_eq!(size_of::<CachePadded<u8>>(), line);
+        assert_eq!(size_of::<[CachePadded<u64>; 2]>(), 2 * line);
+
+        let mut padded = CachePadded::new(1);
+        *padded += 1;
+
+        assert_eq!(format!("{:?}", padded), "CachePadded(2)");
+        assert_eq!(padded.into_inner(), 2);
+    }
+}
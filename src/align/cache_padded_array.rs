@@ -0,0 +1,190 @@
+use std::array::from_fn;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Index, IndexMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::align::CachePadded;
+
+/// The number of threads which have been given an index (see [THREAD_INDEX]).
+static THREAD_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// The index of the current thread, in the order in which threads first used a
+    /// [CachePaddedArray].
+    static THREAD_INDEX: usize = THREAD_COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// An array of `N` [cache-padded](CachePadded) values, e.g. per-thread slots (such as counters)
+/// which are written concurrently without false sharing.
+///
+/// E.g.
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::thread;
+/// use dynrsaur::align::CachePaddedArray;
+///
+/// let counts = CachePaddedArray::<AtomicUsize, 8>::default();
+///
+/// thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| counts.for_current_thread().fetch_add(1, Ordering::Relaxed));
+///     }
+/// });
+///
+/// assert_eq!(counts.iter().map(|count| count.load(Ordering::Relaxed)).sum::<usize>(), 4);
+///
+/// // With no more threads than slots, each thread has its own slot
+/// assert!(counts.iter().all(|count| count.load(Ordering::Relaxed) <= 1));
+/// ```
+///
+/// Generic Parameters:
+/// - `T`: the type of the values.
+/// - `N`: the number of values.
+pub struct CachePaddedArray<T, const N: usize> {
+    slots: [CachePadded<T>; N]
+}
+
+impl<T, const N: usize> CachePaddedArray<T, N> {
+    /// Creates the array, where each value is `f(index)`.
+    pub fn from_fn(mut f: impl FnMut(usize) -> T) -> Self {
+        CachePaddedArray {
+            slots: from_fn(|index| CachePadded::new(f(index)))
+        }
+    }
+
+    /// The number of values.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Whether there are no values.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Borrows the value at `index`, or returns [None] if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.slots.get(index).map(CachePadded::get_ref)
+    }
+
+    /// Mutably borrows the value at `index`, or returns [None] if it is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.slots.get_mut(index).map(CachePadded::get_mut)
+    }
+
+    /// Iterates over the values.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().map(CachePadded::get_ref)
+    }
+
+    /// Iterates mutably over the values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots.iter_mut().map(CachePadded::get_mut)
+    }
+
+    /// The index of the slot of the current thread (see
+    /// [for_current_thread](CachePaddedArray::for_current_thread)).
+    pub fn current_thread_index(&self) -> usize {
+        const { assert!(N > 0, "CachePaddedArray has no slots") };
+
+        THREAD_INDEX.with(|index| index % N)
+    }
+
+    /// Borrows the slot of the current thread.
+    ///
+    /// Each thread always gets the same slot. Threads are given slots in turn, so no slots are
+    /// shared until there are more than `N` threads, but then they are (so the values must
+    /// support concurrent use, e.g. atomics).
+    pub fn for_current_thread(&self) -> &T {
+        &self[self.current_thread_index()]
+    }
+
+    /// Returns the values (which are no longer padded).
+    pub fn into_inner(self) -> [T; N] {
+        self.slots.map(CachePadded::into_inner)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for CachePaddedArray<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.slots[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for CachePaddedArray<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.slots[index]
+    }
+}
+
+impl<T: Default, const N: usize> Default for CachePaddedArray<T, N> {
+    fn default() -> Self {
+        Self::from_fn(|_| T::default())
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for CachePaddedArray<T, N> {
+    fn clone(&self) -> Self {
+        CachePaddedArray {
+            slots: self.slots.clone()
+        }
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for CachePaddedArray<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.iter())
+            .finish()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for CachePaddedArray<T, N> {
+    fn from(values: [T; N]) -> Self {
+        CachePaddedArray {
+            slots: values.map(CachePadded::new)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use crate::align::align_markers::CacheLineAlignment;
+    use crate::align::cache_padded_array::THREAD_INDEX;
+    use crate::align::{CachePaddedArray, ValidAlignment};
+
+    #[test]
+    fn slots() {
+        let mut array = CachePaddedArray::<u8, 4>::from_fn(|index| index as u8);
+
+        array[3] += 1;
+        *array.get_mut(0).unwrap() = 10;
+
+        let first = array.get(0).unwrap() as *const u8 as usize;
+        let second = array.get(1).unwrap() as *const u8 as usize;
+
+        assert_eq!(second - first, CacheLineAlignment::VALUE);
+        assert_eq!(format!("{:?}", array), "[10, 1, 2, 4]");
+        assert_eq!(array.get(4), None);
+        assert_eq!(array.into_inner(), [10, 1, 2, 4]);
+    }
+
+    #[test]
+    fn current_thread() {
+        let array = CachePaddedArray::<u8, 3>::from([0, 1, 2]);
+        let index = array.current_thread_index();
+
+        assert!(index < 3);
+        assert_eq!(array.current_thread_index(), index);
+        assert_eq!(*array.for_current_thread(), index as u8);
+
+        // Each thread is given the next index
+        let indices: Vec<usize> = (0..3)
+            .map(|_| thread::spawn(|| THREAD_INDEX.with(|index| *index)).join().unwrap())
+            .collect();
+
+        assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
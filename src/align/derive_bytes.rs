@@ -0,0 +1,135 @@
+/// Defines a `#[repr(C)]` struct, and implements the requested byte-view traits
+/// ([FromBytes](crate::align::FromBytes), [AsBytes](crate::align::AsBytes) and/or
+/// [Immutable](crate::align::Immutable)) for it.
+///
+/// Each trait is checked at compile time:
+/// - `FromBytes`/`Immutable` require every field to implement the trait (so e.g. a `bool` field,
+///   which has invalid bit-patterns, can't be `FromBytes`),
+/// - `AsBytes` requires every field to implement the trait, and the struct to have no padding.
+///
+/// E.g.
+/// ```
+/// use dynrsaur::align::{AlignedBytes, AsBytes};
+/// use dynrsaur::derive_bytes;
+///
+/// derive_bytes! {
+///     #[derive_bytes(FromBytes, AsBytes, Immutable)]
+///     #[repr(C)]
+///     #[derive(Debug, PartialEq)]
+///     pub struct Header {
+///         pub magic: u32,
+///         pub len: u32
+///     }
+/// }
+///
+/// let mut bytes = AlignedBytes::<16, 8>::zeroed();
+/// bytes.view_mut::<Header>().magic = 0xCAFE;
+///
+/// assert_eq!(bytes.view::<Header>(), &Header { magic: 0xCAFE, len: 0 });
+/// assert_eq!(bytes.view::<Header>().as_bytes(), &bytes[..8]);
+/// ```
+///
+/// Padding is rejected for `AsBytes`:
+/// ```compile_fail
+/// dynrsaur::derive_bytes! {
+///     #[derive_bytes(AsBytes)]
+///     #[repr(C)]
+///     struct Padded {
+///         small: u8,
+///         large: u32
+///     }
+/// }
+/// ```
+///
+/// As are fields with invalid bit-patterns for `FromBytes`:
+/// ```compile_fail
+/// dynrsaur::derive_bytes! {
+///     #[derive_bytes(FromBytes)]
+///     #[repr(C)]
+///     struct Flag {
+///         set: bool
+///     }
+/// }
+/// ```
+///
+/// Generic and tuple structs are not supported. The struct must be `#[repr(C)]` (optionally with
+/// other representation hints, e.g. `#[repr(C, align(16))]`), as the layout of other structs is
+/// unspecified.
+#[macro_export]
+macro_rules! derive_bytes {
+    (
+        #[derive_bytes($($traits:ident),* $(,)?)]
+        #[repr(C $(, $reprs:meta)*)]
+        $(#[$attrs:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attrs:meta])* $field_vis:vis $fields:ident: $types:ty),* $(,)?
+        }
+    ) => {
+        #[repr(C $(, $reprs)*)]
+        $(#[$attrs])*
+        $vis struct $name {
+            $($(#[$field_attrs])* $field_vis $fields: $types),*
+        }
+
+        $crate::derive_bytes!(@impls [$($traits),*] $name; [$($types),*]);
+    };
+    (@impls [$($traits:ident),*] $name:ident; $types:tt) => {
+        $($crate::derive_bytes!(@impl $traits $name; $types);)*
+    };
+    (@impl FromBytes $name:ident; [$($types:ty),*]) => {
+        // SAFETY: Every field is `FromBytes`, and padding can hold any bytes.
+        unsafe impl $crate::align::FromBytes for $name
+            where $($types: $crate::align::FromBytes),*
+        {}
+    };
+    (@impl AsBytes $name:ident; [$($types:ty),*]) => {
+        const _: () = assert!(
+            ::std::mem::size_of::<$name>() == 0 $(+ ::std::mem::size_of::<$types>())*,
+            concat!(stringify!($name), " has padding, so can't be AsBytes")
+        );
+
+        // SAFETY: Every field is `AsBytes`, and (checked above) there is no padding.
+        unsafe impl $crate::align::AsBytes for $name
+            where $($types: $crate::align::AsBytes),*
+        {}
+    };
+    (@impl Immutable $name:ident; [$($types:ty),*]) => {
+        // SAFETY: Every field is `Immutable`.
+        unsafe impl $crate::align::Immutable for $name
+            where $($types: $crate::align::Immutable),*
+        {}
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{AlignedBytes, AsBytes, FromBytes};
+
+    derive_bytes! {
+        #[derive_bytes(FromBytes, Immutable)]
+        #[repr(C, align(16))]
+        #[derive(Debug, PartialEq)]
+        struct Padded {
+            small: u8,
+            large: u32,
+        }
+    }
+
+    derive_bytes! {
+        #[derive_bytes(AsBytes, Immutable)]
+        #[repr(C)]
+        struct Flags {
+            set: [bool; 2],
+            count: u16
+        }
+    }
+
+    #[test]
+    fn derived() {
+        let bytes = AlignedBytes::<32, 16>::from([1; 32]);
+
+        assert_eq!(bytes.view::<Padded>(), &Padded { small: 1, large: 0x01010101 });
+        assert_eq!(Padded::new_zeroed(), Padded { small: 0, large: 0 });
+        assert_eq!(Flags { set: [true, false], count: 0 }.as_bytes(), &[1, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,194 @@
+use sealed::sealed;
+use crate::align::{AlignedBox, AlignedBytes, AlignedCursor, AlignedVec, Alignment, FromBytes};
+use crate::align::valid_alignment::ValidAlignment;
+
+/// Generates the accessors for the given integer types.
+macro_rules! endian_accessors {
+    ($($types:ty),*) => {
+        ::paste::paste! {
+            $(
+                #[doc = concat!(
+                    "Reads a little-endian `", stringify!($types), "` at `offset`, or returns [None] if it ",
+                    "is out of bounds."
+                )]
+                fn [<$types _le_at>](&self, offset: usize) -> Option<$types> {
+                    read_at::<$types>(self.as_byte_slice(), Self::ALIGN, offset).map(<$types>::from_le)
+                }
+
+                #[doc = concat!(
+                    "Reads a big-endian `", stringify!($types), "` at `offset`, or returns [None] if it ",
+                    "is out of bounds."
+                )]
+                fn [<$types _be_at>](&self, offset: usize) -> Option<$types> {
+                    read_at::<$types>(self.as_byte_slice(), Self::ALIGN, offset).map(<$types>::from_be)
+                }
+
+                #[doc = concat!(
+                    "Writes a little-endian `", stringify!($types), "` at `offset`, or returns [None] if it ",
+                    "is out of bounds."
+                )]
+                fn [<set_ $types _le_at>](&mut self, offset: usize, value: $types) -> Option<()> {
+                    write_at(self.as_byte_slice_mut(), Self::ALIGN, offset, value.to_le())
+                }
+
+                #[doc = concat!(
+                    "Writes a big-endian `", stringify!($types), "` at `offset`, or returns [None] if it ",
+                    "is out of bounds."
+                )]
+                fn [<set_ $types _be_at>](&mut self, offset: usize, value: $types) -> Option<()> {
+                    write_at(self.as_byte_slice_mut(), Self::ALIGN, offset, value.to_be())
+                }
+            )*
+        }
+    };
+}
+
+/// Endian-aware access to integers at byte offsets in an aligned buffer, e.g. for wire formats.
+///
+/// Sealed trait that is implemented for [AlignedBytes], [AlignedVec] and [AlignedBox]. Where the
+/// offset of an integer is aligned for it (given the alignment of the buffer), it is accessed
+/// directly, otherwise it is accessed byte-wise.
+///
+/// E.g.
+/// ```
+/// use dynrsaur::align::{AlignedBytes, EndianBytes};
+///
+/// let mut bytes = AlignedBytes::<8, 4>::zeroed();
+///
+/// bytes.set_u32_be_at(0, 0x01020304).unwrap();
+/// bytes.set_u16_le_at(5, 0x0506).unwrap();
+///
+/// assert_eq!(bytes.as_bytes(), &[1, 2, 3, 4, 0, 6, 5, 0]);
+/// assert_eq!(bytes.u32_le_at(0), Some(0x04030201));
+/// assert_eq!(bytes.u16_be_at(7), None);
+/// ```
+#[sealed(pub(in super))]
+pub trait EndianBytes {
+    /// The alignment of the start of the buffer.
+    const ALIGN: usize;
+
+    /// Borrows the bytes of the buffer.
+    fn as_byte_slice(&self) -> &[u8];
+
+    /// Mutably borrows the bytes of the buffer.
+    fn as_byte_slice_mut(&mut self) -> &mut [u8];
+
+    /// Creates a [cursor](AlignedCursor) over the buffer, starting at its beginning.
+    fn cursor(&mut self) -> AlignedCursor<'_, Self> {
+        AlignedCursor::new(self)
+    }
+
+    endian_accessors!(u16, u32, u64, u128, i16, i32, i64, i128);
+}
+
+/// Reads a `T` at `offset` in `bytes` (which are aligned to `align`).
+fn read_at<T: FromBytes + Copy>(bytes: &[u8], align: usize, offset: usize) -> Option<T> {
+    let bytes = bytes.get(offset..offset.checked_add(size_of::<T>())?)?;
+    let ptr = bytes.as_ptr() as *const T;
+
+    // SAFETY: The bytes are in bounds, and are a valid `T` (as any bytes are). The fast path is
+    //         only taken if the start of the buffer, and so `offset` within it, is aligned for a
+    //         `T`.
+    Some(match align_of::<T>() <= align && offset.is_multiple_of(align_of::<T>()) {
+        true => unsafe { ptr.read() },
+        false => unsafe { ptr.read_unaligned() }
+    })
+}
+
+/// Writes `value` at `offset` in `bytes` (which are aligned to `align`).
+fn write_at<T: FromBytes + Copy>(
+    bytes: &mut [u8],
+    align: usize,
+    offset: usize,
+    value: T
+) -> Option<()> {
+    let bytes = bytes.get_mut(offset..offset.checked_add(size_of::<T>())?)?;
+    let ptr = bytes.as_mut_ptr() as *mut T;
+
+    // SAFETY: As for `read_at`.
+    match align_of::<T>() <= align && offset.is_multiple_of(align_of::<T>()) {
+        true => unsafe { ptr.write(value) },
+        false => unsafe { ptr.write_unaligned(value) }
+    }
+
+    Some(())
+}
+
+#[sealed]
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> EndianBytes for AlignedBytes<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    const ALIGN: usize = ALIGN;
+
+    fn as_byte_slice(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        self.as_bytes_mut()
+    }
+}
+
+#[sealed]
+impl<const ALIGN: usize> EndianBytes for AlignedVec<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    const ALIGN: usize = ALIGN;
+
+    fn as_byte_slice(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+#[sealed]
+impl<const ALIGN: usize> EndianBytes for AlignedBox<[u8], ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    const ALIGN: usize = ALIGN;
+
+    fn as_byte_slice(&self) -> &[u8] {
+        self
+    }
+
+    fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{AlignedBox, AlignedVec, EndianBytes};
+
+    #[test]
+    fn aligned_and_unaligned() {
+        let mut vec = AlignedVec::<8>::zeroed(24);
+
+        vec.set_u64_be_at(8, 0x0102030405060708).unwrap();
+        vec.set_u64_le_at(1, 0x0102030405060708).unwrap();
+
+        assert_eq!(&vec[..16], &[0, 8, 7, 6, 5, 4, 3, 2, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(vec.u64_be_at(8), Some(0x0102030405060708));
+        assert_eq!(vec.u64_le_at(1), Some(0x0102030405060708));
+        assert_eq!(vec.i16_be_at(1), Some(0x0807));
+        assert_eq!(vec.set_u128_le_at(9, 0), None);
+        assert_eq!(vec.u32_le_at(usize::MAX), None);
+    }
+
+    #[test]
+    fn boxed() {
+        let mut boxed = AlignedBox::<[u8], 16>::zeroed(16);
+
+        boxed.set_i128_be_at(0, -2).unwrap();
+
+        assert_eq!(boxed[15], 0xFE);
+        assert_eq!(boxed.i128_be_at(0), Some(-2));
+        assert_eq!(boxed.i128_le_at(0), Some((-2i128).swap_bytes()));
+    }
+}
@@ -0,0 +1,72 @@
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use crate::align::{Aligned, AlignedBytes, AlignedUninit, Alignment};
+use crate::align::valid_alignment::ValidAlignment;
+
+/// Indicates that any (initialised) bytes are a valid value of the type, so it can be read
+/// directly from e.g. [AlignedBytes] (see [AlignedBytes::view]).
+///
+/// Implemented for the primitive numeric types, and arrays/[aligned](Aligned) values of
+/// `FromBytes` types. For structs, see [derive_bytes](crate::derive_bytes).
+///
+/// # Safety
+///
+/// Every bit-pattern of `size_of::<Self>()` bytes must be a valid value of the type (e.g. `bool`,
+/// `char`, references and enums with unused discriminants are not `FromBytes`).
+pub unsafe trait FromBytes {
+    /// Creates a value where all bytes are zero.
+    fn new_zeroed() -> Self
+        where Self: Sized
+    {
+        // SAFETY: Zero bytes are a valid value of a `FromBytes` type.
+        unsafe { MaybeUninit::zeroed().assume_init() }
+    }
+
+    /// Reads a value from (possibly unaligned) `bytes`, or returns [None] if the number of bytes
+    /// is not `size_of::<Self>()`.
+    fn read_from(bytes: &[u8]) -> Option<Self>
+        where Self: Sized
+    {
+        match bytes.len() == size_of::<Self>() {
+            // SAFETY: Any bytes are a valid value of a `FromBytes` type.
+            true => Some(unsafe { (bytes.as_ptr() as *const Self).read_unaligned() }),
+            false => None
+        }
+    }
+}
+
+/// Implements [FromBytes] for the given types.
+macro_rules! impl_from_bytes {
+    ($($types:ty),*) => {
+        $(
+            // SAFETY: Any bytes are a valid primitive number.
+            unsafe impl FromBytes for $types {}
+        )*
+    };
+}
+
+impl_from_bytes!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, ());
+
+// SAFETY: Arrays have no more bit-patterns than their elements.
+unsafe impl<T: FromBytes, const N: usize> FromBytes for [T; N] {}
+unsafe impl<T: FromBytes> FromBytes for [T] {}
+
+// SAFETY: ZSTs have exactly one (empty) bit-pattern.
+unsafe impl<T: ?Sized> FromBytes for PhantomData<T> {}
+unsafe impl<const ALIGN: usize> FromBytes for Alignment<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+// SAFETY: Uninitialised storage can hold any bytes (even uninitialised ones).
+unsafe impl<T> FromBytes for MaybeUninit<T> {}
+unsafe impl<const SIZE: usize, const ALIGN: usize> FromBytes for AlignedUninit<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+// SAFETY: Byte-arrays can hold any bytes, and alignment padding can hold any bytes.
+unsafe impl<const SIZE: usize, const ALIGN: usize> FromBytes for AlignedBytes<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+unsafe impl<T: FromBytes, const ALIGN: usize> FromBytes for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
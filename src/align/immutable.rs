@@ -0,0 +1,51 @@
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use crate::align::{Aligned, AlignedBytes, AlignedUninit, Alignment};
+use crate::align::valid_alignment::ValidAlignment;
+
+/// Indicates that the type has no interior mutability (i.e. it contains no
+/// [UnsafeCell](std::cell::UnsafeCell), other than behind an indirection), so its bytes can't
+/// change while it is borrowed.
+///
+/// Required to view a value as bytes (or vice-versa) through a shared reference. For structs, see
+/// [derive_bytes](crate::derive_bytes).
+///
+/// # Safety
+///
+/// The type must not contain an [UnsafeCell](std::cell::UnsafeCell) (e.g. a
+/// [Cell](std::cell::Cell) or an atomic).
+pub unsafe trait Immutable {}
+
+/// Implements [Immutable] for the given types.
+macro_rules! impl_immutable {
+    ($($types:ty),*) => {
+        $(
+            // SAFETY: Primitive types have no interior mutability.
+            unsafe impl Immutable for $types {}
+        )*
+    };
+}
+
+impl_immutable!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, ()
+);
+
+// SAFETY: These types contain no cells other than those in `T`.
+unsafe impl<T: Immutable, const N: usize> Immutable for [T; N] {}
+unsafe impl<T: Immutable> Immutable for [T] {}
+unsafe impl<T: Immutable> Immutable for MaybeUninit<T> {}
+unsafe impl<T: Immutable, const ALIGN: usize> Immutable for Aligned<T, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+
+// SAFETY: These types contain no cells.
+unsafe impl<T: ?Sized> Immutable for PhantomData<T> {}
+unsafe impl<const ALIGN: usize> Immutable for Alignment<ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+unsafe impl<const SIZE: usize, const ALIGN: usize> Immutable for AlignedBytes<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
+unsafe impl<const SIZE: usize, const ALIGN: usize> Immutable for AlignedUninit<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{}
@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::align::MAX_ALIGNMENT;
+
+/// Error returned when a run-time alignment has no [alignment marker](crate::align::align_markers)
+/// (see [with_alignment](crate::with_alignment) and
+/// [check_alignment](crate::align::check_alignment)), or is not the expected alignment (see
+/// [ValidAlignment::check_eq](crate::align::ValidAlignment::check_eq)).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct InvalidAlignment {
+    /// The unsupported alignment.
+    align: usize,
+    /// The expected alignment, if any.
+    expected: Option<usize>
+}
+
+impl InvalidAlignment {
+    /// Creates the error for the unsupported alignment `align`.
+    pub const fn new(align: usize) -> Self {
+        InvalidAlignment { align, expected: None }
+    }
+    
+    /// Creates the error for the alignment `align`, where `expected` was required.
+    pub const fn mismatched(align: usize, expected: usize) -> Self {
+        InvalidAlignment { align, expected: Some(expected) }
+    }
+    
+    /// The unsupported alignment.
+    pub const fn align(&self) -> usize {
+        self.align
+    }
+    
+    /// The expected alignment, if a specific alignment was required.
+    pub const fn expected(&self) -> Option<usize> {
+        self.expected
+    }
+}
+
+impl Display for InvalidAlignment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.align.is_power_of_two() {
+            write!(f, "alignment {} is not a power of two", self.align)
+        } else if self.align > MAX_ALIGNMENT {
+            write!(f, "alignment {} exceeds the maximum alignment ({})", self.align, MAX_ALIGNMENT)
+        } else if let Some(expected) = self.expected {
+            write!(f, "expected alignment {}, but got {}", expected, self.align)
+        } else {
+            write!(f, "alignment {} is not supported", self.align)
+        }
+    }
+}
+
+impl Error for InvalidAlignment {}
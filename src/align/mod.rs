@@ -3,17 +3,75 @@
 //! Contains:
 //! - [ZSTs](align_markers) with specified alignments (e.g. [AlignMarker32](align_markers::AlignMarker32)),
 //! - a [const-generic equivalent](Alignment) for use in generic contexts,
-//! - the [ValidAlignment] trait for use in trait bounds w.r.t. alignment, and,
+//! - the [ValidAlignment] trait for use in trait bounds w.r.t. alignment, and type-level
+//!   [operations](MaxWith) on alignments (e.g. [Max], [AtLeast]),
+//! - the [with_alignment](crate::with_alignment) macro, which dispatches run-time alignments to
+//!   const-generic code,
+//! - [Aligned], which aligns a value of any type with a const-generic parameter `ALIGN`, and
+//!   [CachePadded]/[CachePaddedArray], which align values to a
+//!   [cache-line](align_markers::CacheLineAlignment),
 //! - [AlignedBytes], an extension of `[u8; SIZE]` which also can specify alignment with
-//!   a const-generic parameter `ALIGN`.
+//!   a const-generic parameter `ALIGN`, and which can be [viewed](AlignedBytes::view) as other
+//!   types that are [FromBytes]/[AsBytes]/[Immutable] (see [derive_bytes](crate::derive_bytes)),
+//! - [AlignedUninit], its possibly-uninitialised counterpart, for storing values in-place,
+//! - [AlignedVec]/[AlignedBox], its growable/fixed-size counterparts on the heap,
+//! - [AlignedArena], a fixed-capacity allocator over an [AlignedBytes] buffer, and,
+//! - [endian-aware access](EndianBytes) to integers in these buffers, and [cursors](AlignedCursor)
+//!   over them.
 
 pub mod align_markers;
 
+mod aligned;
+pub use aligned::Aligned;
+
+mod aligned_arena;
+pub use aligned_arena::AlignedArena;
+
+mod aligned_box;
+pub use aligned_box::AlignedBox;
+
 mod aligned_bytes;
 pub use aligned_bytes::AlignedBytes;
 
+mod aligned_cursor;
+pub use aligned_cursor::AlignedCursor;
+
+mod aligned_uninit;
+pub use aligned_uninit::AlignedUninit;
+
+mod aligned_vec;
+pub use aligned_vec::AlignedVec;
+
 mod alignment;
 pub use alignment::Alignment;
 
+mod alignment_ops;
+pub use alignment_ops::{AlignOfMarker, AtLeast, AtMost, Max, MaxWith, Min, MinWith};
+
+mod as_bytes;
+pub use as_bytes::AsBytes;
+
+mod cache_padded;
+pub use cache_padded::CachePadded;
+
+mod cache_padded_array;
+pub use cache_padded_array::CachePaddedArray;
+
+mod derive_bytes;
+
+mod endian_bytes;
+pub use endian_bytes::EndianBytes;
+
+mod from_bytes;
+pub use from_bytes::FromBytes;
+
+mod immutable;
+pub use immutable::Immutable;
+
+mod invalid_alignment;
+pub use invalid_alignment::InvalidAlignment;
+
 mod valid_alignment;
-pub use valid_alignment::ValidAlignment;
+pub use valid_alignment::{check_alignment, ValidAlignment, MAX_ALIGNMENT};
+
+mod with_alignment;
@@ -1,11 +1,42 @@
 use std::fmt::Debug;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 use sealed::sealed;
+use crate::align::InvalidAlignment;
+
+/// The largest [valid alignment](ValidAlignment) on the target platform.
+/// 
+/// This is 2<sup>29</sup> (the largest alignment Rust supports), except on 16-bit platforms, where
+/// values can be at most `isize::MAX` bytes, so it is 2<sup>14</sup>.
+#[cfg(not(target_pointer_width = "16"))]
+pub const MAX_ALIGNMENT: usize = 1 << 29;
+
+/// The largest [valid alignment](ValidAlignment) on the target platform.
+/// 
+/// This is 2<sup>29</sup> (the largest alignment Rust supports), except on 16-bit platforms, where
+/// values can be at most `isize::MAX` bytes, so it is 2<sup>14</sup>.
+#[cfg(target_pointer_width = "16")]
+pub const MAX_ALIGNMENT: usize = 1 << 14;
+
+/// Checks (at run-time) that `align` is a [valid alignment](ValidAlignment), i.e. a power of two
+/// up to [MAX_ALIGNMENT].
+/// 
+/// To check for a specific alignment, see [ValidAlignment::check_eq]. To find the alignment type
+/// for a run-time alignment, see [with_alignment](crate::with_alignment).
+pub const fn check_alignment(align: usize) -> Result<(), InvalidAlignment> {
+    match align.is_power_of_two() && align <= MAX_ALIGNMENT {
+        true => Ok(()),
+        false => Err(InvalidAlignment::new(align))
+    }
+}
 
 /// Indicates that the type represents a valid alignment.
 /// 
 /// Sealed trait that is only implemented for the [alignment marker types](crate::align::align_markers),
 /// e.g. [`AlignMarker32`](crate::align::AlignMarker32), and [`Alignment<ALIGN>`](crate::align::Alignment).
+/// 
+/// For type-level arithmetic on alignments, see [MaxWith](crate::align::MaxWith),
+/// [MinWith](crate::align::MinWith), [AtLeast](crate::align::AtLeast) and
+/// [AtMost](crate::align::AtMost).
 #[sealed(pub(in super))]
 pub trait ValidAlignment {
     /// Const access to the alignment marker for this alignment.
@@ -13,9 +44,74 @@ pub trait ValidAlignment {
     /// As the marker type is a ZST, there is only one possible value. This is that value.
     const MARKER: Self::Marker;
     
+    /// The alignment, in bytes.
+    const VALUE: usize;
+    
+    /// The [const-generic alignment](crate::align::Alignment) of this alignment (see
+    /// [AlignOfMarker](crate::align::AlignOfMarker)).
+    type Alignment: ValidAlignment;
+    
     /// The ZST which marks this alignment.
     type Marker: Default + Copy + Debug
         // We require all the auto-traits as bounds to convince Alignment<ALIGN> that it
         // can auto-inherit them. TODO: Include Freeze auto-trait when on nightly.
         + RefUnwindSafe + Send + Sync + Unpin + UnwindSafe;
+    
+    /// Checks (at run-time) that `align` is this alignment, returning the (ZST) marker value if
+    /// so.
+    /// 
+    /// To check for any valid alignment, see [check_alignment]. To find the alignment type for
+    /// any run-time alignment, see [with_alignment](crate::with_alignment).
+    fn check_eq(align: usize) -> Result<Self::Marker, InvalidAlignment> {
+        match align == Self::VALUE {
+            true => Ok(Self::MARKER),
+            false => Err(InvalidAlignment::mismatched(align, Self::VALUE))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{check_alignment, Alignment, InvalidAlignment, ValidAlignment, MAX_ALIGNMENT};
+    use crate::align::align_markers::AlignMarker64;
+
+    #[test]
+    fn check_valid() {
+        let mut align = 1;
+        
+        while align <= MAX_ALIGNMENT {
+            assert_eq!(check_alignment(align), Ok(()));
+            
+            align *= 2;
+        }
+        
+        let error = check_alignment(0).unwrap_err();
+        assert_eq!(error, InvalidAlignment::new(0));
+        assert_eq!(error.to_string(), "alignment 0 is not a power of two");
+        
+        let error = check_alignment(24).unwrap_err();
+        assert_eq!(error.to_string(), "alignment 24 is not a power of two");
+        
+        let error = check_alignment(MAX_ALIGNMENT * 2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("alignment {} exceeds the maximum alignment ({})", MAX_ALIGNMENT * 2, MAX_ALIGNMENT)
+        );
+    }
+
+    #[test]
+    fn check_eq() {
+        assert!(Alignment::<16>::check_eq(16).is_ok());
+        assert!(AlignMarker64::check_eq(64).is_ok());
+
+        let error = Alignment::<16>::check_eq(8).unwrap_err();
+        assert_eq!(error, InvalidAlignment::mismatched(8, 16));
+        assert_eq!(error.to_string(), "expected alignment 16, but got 8");
+
+        let error = AlignMarker64::check_eq(MAX_ALIGNMENT * 2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("alignment {} exceeds the maximum alignment ({})", MAX_ALIGNMENT * 2, MAX_ALIGNMENT)
+        );
+    }
 }
@@ -0,0 +1,116 @@
+/// Calls the body with the [alignment](crate::align::Alignment) type for a run-time alignment
+/// (e.g. from a [Layout](std::alloc::Layout)), for use in const-generic contexts.
+///
+/// The alignment is matched against every [alignment marker](crate::align::align_markers), and
+/// the body is evaluated with the given identifier aliasing the matching `Alignment<N>`. An
+/// optional second identifier names a `const` of the alignment (none is defined without it), which
+/// can be passed as the `ALIGN` parameter of e.g. [AlignedVec](crate::align::AlignedVec).
+/// Evaluates to `Ok(body)`, or to an [InvalidAlignment](crate::align::InvalidAlignment) error if
+/// there is no marker for the alignment (i.e. it is not a power of two up to
+/// [MAX_ALIGNMENT](crate::align::MAX_ALIGNMENT)).
+///
+/// As the body is instantiated for every alignment, keep it small (e.g. a call to a generic
+/// function), and beware that inline types (e.g. `AlignedBytes`) are up to 512MiB in size at
+/// the largest alignments.
+///
+/// E.g.
+/// ```
+/// use dynrsaur::align::{AlignedVec, InvalidAlignment};
+/// use dynrsaur::with_alignment;
+///
+/// fn aligned_address(align: usize) -> Result<usize, InvalidAlignment> {
+///     with_alignment!(align, |A, ALIGN| {
+///         let buffer = AlignedVec::<ALIGN>::zeroed(16);
+///
+///         assert_eq!(align_of::<A>(), align);
+///
+///         buffer.as_ptr() as usize % align
+///     })
+/// }
+///
+/// assert_eq!(aligned_address(64), Ok(0));
+/// assert_eq!(aligned_address(48), Err(InvalidAlignment::new(48)));
+/// ```
+#[macro_export]
+macro_rules! with_alignment {
+    ($align:expr, |$typ:ident $(, $value:ident)?| $body:expr) => {{
+        // Matches the markers available on the target (see `MAX_ALIGNMENT`)
+        #[cfg(target_pointer_width = "16")]
+        let result = $crate::with_alignment!(
+            @arms $align, $typ, [$($value)?], $body;
+            1 2 4 8 16 32 64 128 256 512 1024 2048 4096 8192 16384
+        );
+        
+        #[cfg(not(target_pointer_width = "16"))]
+        let result = $crate::with_alignment!(
+            @arms $align, $typ, [$($value)?], $body;
+            1 2 4 8 16 32 64 128 256 512 1024 2048 4096 8192 16384 32768 65536 131072 262144
+            524288 1048576 2097152 4194304 8388608 16777216 33554432 67108864 134217728
+            268435456 536870912
+        );
+        
+        result
+    }};
+    (@arms $align:expr, $typ:ident, $value:tt, $body:expr; $($aligns:literal)*) => {
+        match $align {
+            $(
+                $aligns => {
+                    #[allow(dead_code)]
+                    type $typ = $crate::align::Alignment<$aligns>;
+                    $crate::with_alignment!(@value $value = $aligns);
+                    
+                    ::core::result::Result::Ok($body)
+                }
+            )*
+            align => ::core::result::Result::Err($crate::align::InvalidAlignment::new(align))
+        }
+    };
+    // Only defines the value `const` if it was named
+    (@value [] = $align:literal) => {};
+    (@value [$value:ident] = $align:literal) => {
+        #[allow(dead_code)]
+        const $value: usize = $align;
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::align::{InvalidAlignment, MAX_ALIGNMENT};
+
+    #[test]
+    fn every_marker() {
+        let mut align = 1;
+        
+        while align <= MAX_ALIGNMENT {
+            assert_eq!(with_alignment!(align, |A| align_of::<A>()), Ok(align));
+            
+            align *= 2;
+        }
+    }
+
+    #[test]
+    fn single_identifier_defines_no_const() {
+        fn align_of_marker<const ALIGN: usize>() -> Result<usize, InvalidAlignment> {
+            // The outer `ALIGN` remains visible in the body
+            with_alignment!(ALIGN * 2, |A| align_of::<A>() + ALIGN)
+        }
+        
+        assert_eq!(align_of_marker::<4>(), Ok(12));
+    }
+
+    #[test]
+    fn invalid() {
+        let error = with_alignment!(0, |A| ()).unwrap_err();
+        assert_eq!(error, InvalidAlignment::new(0));
+        assert_eq!(error.to_string(), "alignment 0 is not a power of two");
+        
+        let error = with_alignment!(24, |A| ()).unwrap_err();
+        assert_eq!(error.to_string(), "alignment 24 is not a power of two");
+        
+        let error = with_alignment!(MAX_ALIGNMENT * 2, |A| ()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("alignment {} exceeds the maximum alignment ({})", MAX_ALIGNMENT * 2, MAX_ALIGNMENT)
+        );
+    }
+}
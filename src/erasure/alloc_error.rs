@@ -0,0 +1,37 @@
+use std::alloc::Layout;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Error returned when the heap allocation for an erasure fails (see [TryErase]).
+/// 
+/// [TryErase]: crate::erasure::TryErase
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AllocError {
+    /// The layout of the allocation that failed.
+    layout: Layout
+}
+
+impl AllocError {
+    /// Creates the error for a failed allocation of `layout`.
+    pub fn new(layout: Layout) -> Self {
+        AllocError { layout }
+    }
+    
+    /// The layout of the allocation that failed.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "memory allocation of {} bytes (aligned to {}) failed",
+            self.layout.size(),
+            self.layout.align()
+        )
+    }
+}
+
+impl Error for AllocError {}
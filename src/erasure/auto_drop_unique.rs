@@ -1,10 +1,19 @@
+use std::alloc::{alloc, dealloc, handle_alloc_error, Layout};
 use std::any::{type_name};
 use std::marker::PhantomData;
-use std::mem::{forget, ManuallyDrop, transmute};
+use std::mem::{forget, ManuallyDrop, MaybeUninit, transmute};
 use std::ops::{Deref, DerefMut};
-use std::ptr::{addr_of_mut, NonNull, read};
+use std::ptr::{addr_of_mut, copy_nonoverlapping, drop_in_place, NonNull, read};
+use sealed::sealed;
 use crate::erasure::erased::Erased;
-use crate::erasure::{Erase, Erasure};
+use crate::erasure::{AllocError, Erase, Erasure, ErasureMut, ErasureRef, TryErase, Unknown};
+use crate::erasure::TranscodeErrorKind;
+use crate::erasure::unknown::metadata_of;
+use crate::util::{try_box_uninit, with_data_pointer, CPair};
+#[cfg(feature = "verify-types")]
+use crate::erasure::type_verification::verify_type_info;
+#[cfg(feature = "telemetry")]
+use crate::erasure::telemetry::{record, TelemetryEventKind};
 
 // TODO: Add ?Sized capability once core::ptr::Pointee stabilised
 
@@ -28,31 +37,99 @@ impl<T> AutoDropUnique<T> {
     /// 
     /// This will allocate the value onto the heap, if is not a ZST.
     pub fn new(value: T) -> Self {
-        let tagged_pointer = match size_of::<T>() {
+        Self::try_new(value).unwrap_or_else(|error| handle_alloc_error(error.layout()))
+    }
+    
+    /// Takes ownership of the given value, as [AutoDropUnique::new], but returns an error
+    /// (dropping the value) instead of aborting if the allocation fails.
+    pub fn try_new(value: T) -> Result<Self, AllocError> {
+        // SAFETY: `write` initialises the slot.
+        unsafe { Self::try_emplace(|slot| { slot.write(value); }) }
+    }
+    
+    /// Takes ownership of the value returned by `f`, which is only called once the allocation has
+    /// succeeded.
+    /// 
+    /// The value may still be passed through the stack (the compiler may or may not write it
+    /// directly into the allocation). To guarantee that it isn't, initialise it in-place via
+    /// [AutoDropUnique::new_uninit].
+    pub fn new_with(f: impl FnOnce() -> T) -> Self {
+        Self::try_new_with(f).unwrap_or_else(|error| handle_alloc_error(error.layout()))
+    }
+    
+    /// Takes ownership of the value returned by `f`, as [AutoDropUnique::new_with], but returns an
+    /// error (without calling `f`) instead of aborting if the allocation fails.
+    pub fn try_new_with(f: impl FnOnce() -> T) -> Result<Self, AllocError> {
+        // SAFETY: `write` initialises the slot.
+        unsafe { Self::try_emplace(|slot| { slot.write(f()); }) }
+    }
+    
+    /// Allocates space for a `T` without initialising it.
+    /// 
+    /// The value can then be initialised in-place (e.g. field by field, via
+    /// [MaybeUninit::as_mut_ptr]), and [assumed to be initialised](AutoDropUnique::assume_init).
+    /// Until then, dropping the pointer doesn't drop the value.
+    pub fn new_uninit() -> AutoDropUnique<MaybeUninit<T>> {
+        AutoDropUnique::<T>::try_new_uninit().unwrap_or_else(|error| handle_alloc_error(error.layout()))
+    }
+    
+    /// Allocates space for a `T` without initialising it, as [AutoDropUnique::new_uninit], but
+    /// returns an error instead of aborting if the allocation fails.
+    pub fn try_new_uninit() -> Result<AutoDropUnique<MaybeUninit<T>>, AllocError> {
+        // SAFETY: `MaybeUninit` doesn't need initialising.
+        unsafe { AutoDropUnique::<MaybeUninit<T>>::try_emplace(|_| {}) }
+    }
+    
+    /// Allocates the slot for the value, and initialises it with `init`.
+    /// 
+    /// If `init` panics, the allocation is freed (the slot is not dropped).
+    /// 
+    /// SAFETY: `init` must initialise the slot.
+    unsafe fn try_emplace(init: impl FnOnce(&mut MaybeUninit<T>)) -> Result<Self, AllocError> {
+        let location = match size_of::<T>() {
             0 => {
+                let mut slot = MaybeUninit::<T>::uninit();
+                
                 // Throw away the value without dropping, as we can trivially recreate it later.
-                forget(value);
+                init(&mut slot);
                 
-                StackOrHeap::Stack(Self::zst_drop_impl) 
+                StackOrHeap::Stack(metadata_of::<T>)
             },
-            _ => StackOrHeap::Heap(
-                NonNull::from(
-                    Box::leak(
-                        Box::new(
-                            CPair(
-                                Self::non_zst_drop_impl as unsafe fn(NonNull<()>),
-                                ManuallyDrop::new(value)
-                            )
+            _ => {
+                let mut inner = try_box_uninit::<AutoDropUniqueInner<T>>()?;
+                
+                init(&mut *(addr_of_mut!((*inner.as_mut_ptr()).1) as *mut MaybeUninit<T>));
+                
+                let inner_ptr = Box::into_raw(inner) as *mut AutoDropUniqueInner<T>;
+                
+                let value_ptr = addr_of_mut!((*inner_ptr).1) as *mut u8;
+                
+                addr_of_mut!((*inner_ptr).0).write(
+                    AutoDropUniqueHeader {
+                        object: NonNull::new_unchecked(
+                            with_data_pointer(metadata_of::<T>().as_ptr(), value_ptr)
                         )
-                    )
-                )
-            )
+                    }
+                );
+                
+                StackOrHeap::Heap(NonNull::new_unchecked(inner_ptr).cast())
+            }
         };
-
-        AutoDropUnique {
-            tagged_pointer: tagged_pointer.into_usize(),
+        
+        Ok(Self::from_location(location))
+    }
+    
+    /// Takes ownership of the value in `location`, which must be a `T`.
+    fn from_location(location: StackOrHeap) -> Self {
+        let unique = Self {
+            tagged_pointer: location.into_usize(),
             _marker: PhantomData
-        }
+        };
+        
+        #[cfg(feature = "telemetry")]
+        unique.record(TelemetryEventKind::Erased);
+        
+        unique
     }
     
     /// Erases the type of the underlying value.
@@ -77,11 +154,14 @@ impl<T> AutoDropUnique<T> {
     pub fn into_inner(self) -> T {
         self.assert_not_erased();
         
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Downcast);
+        
         match self.take_inner() {
             StackOrHeap::Stack(_) => Self::zst_instance(),
             StackOrHeap::Heap(inner) => {
-                // SAFETY: created by Box::leak
-                let boxed = unsafe { Box::from_raw(inner.as_ptr()) };
+                // SAFETY: created by Box::into_raw (or allocated with the same layout)
+                let boxed = unsafe { Box::from_raw(inner.cast::<AutoDropUniqueInner<T>>().as_ptr()) };
 
                 let value = (*boxed).1;
 
@@ -91,39 +171,13 @@ impl<T> AutoDropUnique<T> {
     }
     
     /// Takes ownership of the inner representation.
-    fn take_inner(self) -> StackOrHeap<T> {
+    fn take_inner(self) -> StackOrHeap {
         let location = self.location();
         
         forget(self);
         
         location
     }
-
-    /// Implementation of drop which remembers the type of the underlying value.
-    /// 
-    /// Takes the inner value of the AutoDropUnique, cast to a `NonNull<()>`.
-    /// 
-    /// SAFETY: Must only be called when dropping the AutoDropUnique, and `inner` must be
-    ///         the inner value of the AutoDropUnique [cast to unit](NonNull::cast).
-    unsafe fn non_zst_drop_impl(inner: NonNull<()>) {
-        assert_ne!(size_of::<T>(), 0);
-        
-        // Cast the inner pointer back to its original type
-        let mut ptr = inner.cast::<AutoDropUniqueInner<T>>();
-
-        // Extract the value from the inner pair and drop it
-        let value_mut = &mut ptr.as_mut().1;
-        ManuallyDrop::drop(value_mut);
-        
-        // Drop the heap memory
-        drop(Box::from_raw(ptr.as_ptr()))
-    }
-    
-    /// Drop implementation for ZSTs.
-    fn zst_drop_impl() {
-        // As ZSTs are not stored, generate a new instance of the ZST, and then drops it.
-        let _zst: T = Self::zst_instance();
-    }
     
     /// Helper function to generate a new instance of a ZST.
     /// 
@@ -134,26 +188,47 @@ impl<T> AutoDropUnique<T> {
         unsafe { read(NonNull::<Z>::dangling().as_ptr()) }
     }
     
-    fn location(&self) -> StackOrHeap<T> {
+    fn location(&self) -> StackOrHeap {
         // SAFETY: Created with StackOrHeap::into_usize.
         unsafe {
-            StackOrHeap::<T>::from_usize(self.tagged_pointer)
+            StackOrHeap::from_usize(self.tagged_pointer)
         }
     }
     
-    /// Returns a pointer to the value.
-    fn as_ptr(&self) -> *mut T {
+    /// Returns a pointer to the value, with the metadata of its (original) type.
+    fn object(&self) -> NonNull<dyn Unknown> {
         match self.location() {
-            StackOrHeap::Stack(_) => NonNull::<T>::dangling().as_ptr(),
-            StackOrHeap::Heap(inner) => {
-                let inner_ptr = inner.as_ptr();
-                // SAFETY: `inner_ptr` is from NonNull.
-                let inner = unsafe { addr_of_mut!((*inner_ptr).1) };
-                inner as *mut T
-            }
+            // ZSTs can be accessed at any aligned address
+            StackOrHeap::Stack(metadata) => metadata(),
+            // SAFETY: The header is valid for the lifetime of the AutoDropUnique.
+            StackOrHeap::Heap(inner) => unsafe { inner.as_ref().object }
         }
     }
     
+    /// Returns a pointer to the value.
+    fn as_ptr(&self) -> *mut T {
+        self.object().as_ptr() as *mut T
+    }
+    
+    /// Records a [telemetry event](crate::erasure::telemetry) for the value (as an erased
+    /// `AutoDropUnique`, which is the type held by containers).
+    #[cfg(feature = "telemetry")]
+    fn record(&self, kind: TelemetryEventKind) {
+        // SAFETY: The object is valid for the lifetime of the AutoDropUnique.
+        let object = unsafe { self.object().as_ref() };
+        
+        let (type_name, size) = (object.__type_info().1, size_of_val(object));
+        
+        record::<AutoDropUnique>(kind, "AutoDropUnique", type_name, size)
+    }
+    
+    /// Panics if the underlying value is not a `U`.
+    #[cfg(feature = "verify-types")]
+    fn verify_type<U>(&self) {
+        // SAFETY: The object is valid for the lifetime of the AutoDropUnique.
+        verify_type_info::<U>(unsafe { self.object().as_ref() }.__type_info())
+    }
+    
     /// Panics if `T` is uninhabited (i.e. `T` is [`Erased`]).
     fn assert_not_erased(&self) {
         // Detect if T is uninhabited. `size_of::<T>()` is zero for uninhabited types (identical
@@ -165,6 +240,45 @@ impl<T> AutoDropUnique<T> {
     }
 }
 
+impl<T> AutoDropUnique<MaybeUninit<T>> {
+    /// Converts to a pointer to the (initialised) `T`, which will then be dropped with the pointer.
+    /// 
+    /// # Safety
+    /// 
+    /// The value must have been fully initialised.
+    pub unsafe fn assume_init(self) -> AutoDropUnique<T> {
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Downcast);
+        
+        let location = match self.take_inner() {
+            StackOrHeap::Stack(_) => StackOrHeap::Stack(metadata_of::<T>),
+            StackOrHeap::Heap(inner) => {
+                let header = inner.as_ptr();
+                
+                // Replace the metadata, so the value is dropped (and verified) as a `T`
+                (*header).object = NonNull::new_unchecked(
+                    with_data_pointer(
+                        metadata_of::<T>().as_ptr(),
+                        (*header).object.as_ptr() as *mut u8
+                    )
+                );
+                
+                StackOrHeap::Heap(inner)
+            }
+        };
+        
+        AutoDropUnique::from_location(location)
+    }
+    
+    /// Initialises the value in-place, converting to a pointer to the `T`.
+    pub fn write(mut self, value: T) -> AutoDropUnique<T> {
+        (*self).write(value);
+        
+        // SAFETY: Initialised above.
+        unsafe { self.assume_init() }
+    }
+}
+
 impl<T> Deref for AutoDropUnique<T> {
     type Target = T;
 
@@ -183,21 +297,33 @@ impl<T> DerefMut for AutoDropUnique<T> {
 
 impl<T> Drop for AutoDropUnique<T> {
     fn drop(&mut self) {
-        match self.location() {
-            StackOrHeap::Stack(drop) => drop(),
-            StackOrHeap::Heap(inner) => {
-                // SAFETY: inner is unique during drop
-                let drop = unsafe { inner.as_ref().0 };
-
-                // SAFETY: Called during drop with inner cast to unit
-                unsafe { drop(inner.cast::<()>()) }
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Dropped);
+        
+        let object = self.object();
+        
+        // The layout must be taken while the object is still valid.
+        // SAFETY: The object is valid.
+        let layout = Layout::for_value(unsafe { object.as_ref() });
+        
+        // SAFETY: The object is valid, and only dropped once.
+        unsafe { drop_in_place(object.as_ptr()) };
+        
+        if let StackOrHeap::Heap(inner) = self.location() {
+            // SAFETY: The allocation is unique during drop, and has the inner layout of the
+            //         object.
+            unsafe {
+                dealloc(inner.as_ptr() as *mut u8, AutoDropUniqueHeader::inner_layout(layout))
             }
-        };
+        }
     }
 }
 
 impl<T> Erasure<T> for AutoDropUnique {
     unsafe fn downcast_unchecked(self) -> T {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+        
         transmute::<_, AutoDropUnique<T>>(self).into_inner()
     }
 }
@@ -208,32 +334,97 @@ impl<T> Erase<T> for AutoDropUnique {
     }
 }
 
-impl<'borrow, T> Erasure<&'borrow mut T> for &'borrow mut AutoDropUnique {
-    unsafe fn downcast_unchecked(self) -> &'borrow mut T {
-        &mut *(self.as_ptr() as *mut T)
+impl<T> TryErase<T> for AutoDropUnique {
+    fn try_erase(value: T) -> Result<Self, AllocError> {
+        AutoDropUnique::<T>::try_new(value).map(AutoDropUnique::erase)
     }
 }
 
-impl<'borrow, T> Erasure<&'borrow T> for &'borrow AutoDropUnique {
-    unsafe fn downcast_unchecked(self) -> &'borrow T {
+impl ErasureRef for AutoDropUnique {
+    unsafe fn downcast_ref_unchecked<T>(&self) -> &T {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+        
         &*(self.as_ptr() as *const T)
     }
 }
 
+impl ErasureMut for AutoDropUnique {
+    unsafe fn downcast_mut_unchecked<T>(&mut self) -> &mut T {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+        
+        &mut *(self.as_ptr() as *mut T)
+    }
+}
+
+#[sealed]
+impl crate::erasure::transcode::TranscodeSource for AutoDropUnique {
+    fn object(&self) -> NonNull<dyn Unknown> {
+        AutoDropUnique::object(self)
+    }
+
+    unsafe fn forget_value(self) {
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Downcast);
+        
+        let object = self.object();
+        
+        if let StackOrHeap::Heap(inner) = self.take_inner() {
+            dealloc(
+                inner.as_ptr() as *mut u8,
+                AutoDropUniqueHeader::inner_layout(Layout::for_value(object.as_ref()))
+            )
+        }
+    }
+}
+
+#[sealed]
+impl crate::erasure::transcode::TranscodeTarget for AutoDropUnique {
+    unsafe fn read_from(object: NonNull<dyn Unknown>) -> Result<Self, TranscodeErrorKind> {
+        let value_layout = Layout::for_value(object.as_ref());
+        
+        // N.B. ZSTs are also placed on the heap, as the `Stack` representation needs their type
+        let (layout, offset) = AutoDropUniqueHeader::inner_layout_and_offset(value_layout);
+        
+        // SAFETY: The layout is non-zero-sized, as it contains the header.
+        let inner_ptr = alloc(layout);
+        
+        if inner_ptr.is_null() {
+            return Err(TranscodeErrorKind::Alloc);
+        }
+        
+        let value_ptr = inner_ptr.add(offset);
+        
+        copy_nonoverlapping(object.as_ptr() as *const u8, value_ptr, value_layout.size());
+        
+        let header = inner_ptr as *mut AutoDropUniqueHeader;
+        
+        header.write(
+            AutoDropUniqueHeader {
+                object: NonNull::new_unchecked(with_data_pointer(object.as_ptr(), value_ptr))
+            }
+        );
+        
+        Ok(AutoDropUnique::from_location(StackOrHeap::Heap(NonNull::new_unchecked(header))))
+    }
+}
+
 /// Inner data-structure that determines what information needs storing about a value, and where
 /// it should be stored.
 /// 
 /// Converts to/from a word-sized "tagged-pointer" representation, where the LSB of the 
 /// `fn`/`NonNull` pointer is used to tag from which variant it was created.
-enum StackOrHeap<T> {
-    /// `T` is zero-sized, so we don't need to store it at all. Retain a `drop` function which
-    /// regenerates the value of `T` and then drops it.
-    Stack(fn()),
-    /// `T` is non-zero-sized, so we place it with its `drop` function on the heap.
-    Heap(NonNull<AutoDropUniqueInner<T>>)
+enum StackOrHeap {
+    /// The value is zero-sized, so we don't need to store it at all. Retain a function which
+    /// returns a pointer to a ZST with its metadata (see [metadata_of]).
+    Stack(fn() -> NonNull<dyn Unknown>),
+    /// The value is (usually) non-zero-sized, so we place it with its [header](AutoDropUniqueHeader)
+    /// on the heap.
+    Heap(NonNull<AutoDropUniqueHeader>)
 }
 
-impl<T> StackOrHeap<T> {
+impl StackOrHeap {
     /// Mask used to extract the discriminant from the tagged-pointer representation.
     const DISCRIMINANT_MASK: usize = 1;
 
@@ -257,22 +448,43 @@ impl<T> StackOrHeap<T> {
         
         match heap {
             false => Self::Stack(transmute(address)),
-            true => Self::Heap(NonNull::new_unchecked(address as *mut AutoDropUniqueInner<T>)),
+            true => Self::Heap(NonNull::new_unchecked(address as *mut AutoDropUniqueHeader)),
         }
     }
 }
 
-/// A C-like pair of values.
-#[repr(C)]
-struct CPair<A, B: ?Sized>(pub A, pub B);
+/// The information about the value of an [AutoDropUnique] stored alongside it on the heap.
+struct AutoDropUniqueHeader {
+    /// Pointer to the value (in the same allocation), whose metadata provides its layout, drop
+    /// glue and (with the `verify-types` feature) type.
+    object: NonNull<dyn Unknown>
+}
+
+impl AutoDropUniqueHeader {
+    /// The layout of the [AutoDropUniqueInner] of a value with the given layout.
+    fn inner_layout(value_layout: Layout) -> Layout {
+        Self::inner_layout_and_offset(value_layout).0
+    }
+    
+    /// The layout of the [AutoDropUniqueInner] of a value with the given layout, and the offset
+    /// of the value within it.
+    fn inner_layout_and_offset(value_layout: Layout) -> (Layout, usize) {
+        let (layout, offset) = Layout::new::<AutoDropUniqueHeader>()
+            .extend(value_layout)
+            .expect("value layouts are valid");
+        
+        // Matches the `#[repr(C)]` layout of the pair
+        (layout.pad_to_align(), offset)
+    }
+}
 
 /// The heap data-structure held by [AutoDropUnique].
 /// 
 /// A C-like pair of:
-/// 1. a [pointer to the `drop` implementation](AutoDropUnique::non_zst_drop_impl) for the value, and,
+/// 1. the [header](AutoDropUniqueHeader) for the value, and,
 /// 2. the value itself.
 type AutoDropUniqueInner<T> = CPair<
-    unsafe fn (NonNull<()>),
+    AutoDropUniqueHeader,
     ManuallyDrop<T>
 >;
 
@@ -281,6 +493,7 @@ mod tests {
     use std::fmt::Debug;
     use std::hint::black_box;
     use std::ops::{Deref, DerefMut};
+    use std::ptr::addr_of_mut;
     use std::sync::atomic::{AtomicBool, Ordering};
     use crate::erasure::{AutoDropUnique, Erasure};
     use crate::erasure::auto_drop_unique::AutoDropUniqueInner;
@@ -313,13 +526,10 @@ mod tests {
         const WORD_ALIGN: usize = align_of::<usize>();
 
         const fn expected_auto_drop_inner_size<T>() -> usize {
-            let t_size = size_of::<T>();
-            let t_align = align_of::<T>();
-            if t_align >= WORD_ALIGN {
-                t_align + t_size
-            } else {
-                2 * WORD_SIZE
-            }
+            // The value follows the header, which is a wide (2-word) pointer
+            let offset = (2 * WORD_SIZE).next_multiple_of(align_of::<T>());
+            
+            (offset + size_of::<T>()).next_multiple_of(expected_auto_drop_inner_align::<T>())
         }
 
         const fn expected_auto_drop_inner_align<T>() -> usize {
@@ -516,4 +726,78 @@ mod tests {
 
         let _erased_deref_mut = black_box(erased.deref_mut());
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[cfg(feature = "verify-types")]
+    #[should_panic(expected = "downcast to 'u32', but the underlying type is 'alloc::string::String'")]
+    fn mismatched_downcast_panics() {
+        let erased = AutoDropUnique::new(String::from("Test String")).erase();
+
+        let _unerased: u32 = unsafe { erased.downcast_unchecked() };
+    }
+
+    #[test]
+    #[cfg(feature = "verify-types")]
+    #[should_panic(expected = "downcast to 'u8', but the underlying type is '()'")]
+    fn mismatched_zst_downcast_ref_panics() {
+        let erased = AutoDropUnique::new(()).erase();
+
+        let _unerased: &u8 = unsafe { (&erased).downcast_unchecked() };
+    }
+
+    #[test]
+    fn new_with() {
+        let unique = AutoDropUnique::new_with(|| [42u64; 4096]);
+
+        assert!(unique.iter().all(|value| *value == 42));
+    }
+
+    #[test]
+    fn new_uninit() {
+        struct Pair {
+            name: String,
+            values: [u32; 1024]
+        }
+
+        let mut unique = AutoDropUnique::<Pair>::new_uninit();
+
+        let pair = unique.as_mut_ptr();
+
+        let unique = unsafe {
+            addr_of_mut!((*pair).name).write(String::from("test"));
+            addr_of_mut!((*pair).values).write([7; 1024]);
+
+            unique.assume_init()
+        };
+
+        let erased = unique.erase();
+
+        let unerased: Pair = unsafe { erased.downcast_unchecked() };
+
+        assert_eq!(unerased.name, "test");
+        assert_eq!(unerased.values[1023], 7);
+    }
+
+    #[test]
+    fn write_drops_as_initialised_type() {
+        static DROPPED: AtomicBool = AtomicBool::new(false);
+
+        struct SetTrueOnDrop;
+
+        impl Drop for SetTrueOnDrop {
+            fn drop(&mut self) {
+                DROPPED.store(true, Ordering::Release);
+            }
+        }
+
+        {
+            let uninit = AutoDropUnique::<(SetTrueOnDrop, u8)>::new_uninit();
+
+            let _erased = uninit.write((SetTrueOnDrop, 1)).erase();
+
+            assert!(!DROPPED.load(Ordering::Acquire));
+        }
+
+        assert!(DROPPED.load(Ordering::Acquire));
+    }
+}
@@ -0,0 +1,28 @@
+use sealed::sealed;
+use crate::erasure::Unknown;
+
+/// The auto traits (`Send`/`Sync`) of a value, for erasures which can't derive them from the type
+/// the value is accessed as (e.g. [ThinBox](crate::erasure::ThinBox) and
+/// [InlineDyn](crate::erasure::InlineDyn), whose `Dyn` is produced by a caller-supplied coercion,
+/// so may have auto traits the value lacks).
+///
+/// Sealed trait that is implemented by `dyn Unknown` with each combination of `Send` and `Sync`,
+/// for any `T` that has (at least) those auto traits. E.g. `dyn Unknown + Send` is
+/// `AutoTraitsOf<T>` for any `T: Send`, so an erasure recording it is `Send`.
+///
+/// Generic Parameters:
+/// - `T`: the type of the value.
+#[sealed(pub(in super))]
+pub trait AutoTraitsOf<T: ?Sized> {}
+
+#[sealed]
+impl<T: ?Sized> AutoTraitsOf<T> for dyn Unknown {}
+
+#[sealed]
+impl<T: ?Sized + Send> AutoTraitsOf<T> for dyn Unknown + Send {}
+
+#[sealed]
+impl<T: ?Sized + Sync> AutoTraitsOf<T> for dyn Unknown + Sync {}
+
+#[sealed]
+impl<T: ?Sized + Send + Sync> AutoTraitsOf<T> for dyn Unknown + Send + Sync {}
@@ -1,4 +1,3 @@
-use std::any::type_name;
 use crate::erasure::TaggedErasure;
 
 /// Default implementation of [downcast_unchecked](crate::erasure::Erasure::downcast_unchecked).
@@ -15,8 +14,8 @@ pub fn defer_downcast_unchecked_to_try_downcast<
 >(
     self_: E
 ) -> T {
-    match self_.try_downcast() {
+    match self_.try_downcast_or_error() {
         Ok(unerased) => unerased,
-        Err(_) => panic!("invalid downcast to '{}'", type_name::<T>())
+        Err(error) => panic!("{}", error)
     }
 }
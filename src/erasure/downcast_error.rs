@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Error returned when a [tagged erasure](crate::erasure::TaggedErasure) is downcast to a type
+/// which it is not an erasure of.
+/// 
+/// Holds the original erasure, so that it can be [reclaimed](DowncastError::into_erasure), along
+/// with the names of the expected type and (if the erasure's tag records it) the actual
+/// underlying type.
+/// 
+/// Generic Parameters:
+/// - `E`: the type of the erasure.
+pub struct DowncastError<E> {
+    /// The erasure that failed to downcast.
+    erasure: E,
+    /// The name of the type the erasure was downcast to.
+    expected: &'static str,
+    /// The name of the actual underlying type of the erasure, if known.
+    actual: Option<&'static str>
+}
+
+impl<E> DowncastError<E> {
+    /// Creates the error for a failed downcast of `erasure` to the type named `expected`.
+    pub fn new(erasure: E, expected: &'static str, actual: Option<&'static str>) -> Self {
+        DowncastError { erasure, expected, actual }
+    }
+    
+    /// The name of the type the erasure was downcast to.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+    
+    /// The name of the actual underlying type of the erasure, if known.
+    pub fn actual(&self) -> Option<&'static str> {
+        self.actual
+    }
+    
+    /// Borrows the erasure that failed to downcast.
+    pub fn erasure(&self) -> &E {
+        &self.erasure
+    }
+    
+    /// Reclaims the erasure that failed to downcast.
+    pub fn into_erasure(self) -> E {
+        self.erasure
+    }
+}
+
+impl<E> Display for DowncastError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid downcast to '{}'", self.expected)?;
+        
+        if let Some(actual) = self.actual {
+            write!(f, " (underlying type is '{}')", actual)?;
+        }
+        
+        Ok(())
+    }
+}
+
+impl<E> Debug for DowncastError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DowncastError")
+            .field("expected", &self.expected)
+            .field("actual", &self.actual)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> Error for DowncastError<E> {}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use crate::erasure::{ErasedError, TaggedErasure};
+
+    #[test]
+    fn question_mark() {
+        fn downcast(erased: Box<dyn Any>) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(erased.try_downcast_or_error()?)
+        }
+
+        assert_eq!(downcast(Box::new(String::from("test"))).unwrap(), "test");
+
+        let error = downcast(Box::new(42u32)).unwrap_err();
+
+        assert_eq!(error.to_string(), "invalid downcast to 'alloc::string::String'");
+    }
+
+    #[test]
+    fn actual_type_name() {
+        let erased = ErasedError::new(std::fmt::Error);
+
+        let error = TaggedErasure::<std::io::Error>::try_downcast_or_error(erased).unwrap_err();
+
+        assert_eq!(error.expected(), "std::io::error::Error");
+        assert_eq!(error.actual(), Some("core::fmt::Error"));
+        assert_eq!(
+            error.to_string(),
+            "invalid downcast to 'std::io::error::Error' (underlying type is 'core::fmt::Error')"
+        );
+
+        let erased = error.into_erasure();
+
+        assert!(TaggedErasure::<std::fmt::Error>::is(&erased));
+    }
+}
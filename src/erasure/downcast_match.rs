@@ -0,0 +1,98 @@
+/// Branches on the underlying type of a [tagged erasure](crate::erasure::TaggedErasure).
+///
+/// Each arm names a binding and a type. The type of each arm is tried in turn, and the
+/// downcast value is moved into the first arm whose type matches. If no arm matches, the erasure
+/// is passed to the final (fallback) arm, which takes a pattern.
+///
+/// Works for any `TaggedErasure`, including borrowed erasures (where the arm types are then
+/// references).
+///
+/// E.g.
+/// ```
+/// use std::any::Any;
+/// use dynrsaur::downcast_match;
+///
+/// fn describe(erased: &dyn Any) -> String {
+///     downcast_match!(erased => {
+///         string: &String => format!("string '{}'", string),
+///         number: &u32 => format!("number {}", number),
+///         _ => String::from("something else"),
+///     })
+/// }
+///
+/// assert_eq!(describe(&String::from("test")), "string 'test'");
+/// assert_eq!(describe(&42u32), "number 42");
+/// assert_eq!(describe(&42i32), "something else");
+/// ```
+#[macro_export]
+macro_rules! downcast_match {
+    ($erasure:expr => { $($arms:tt)* }) => {{
+        let erasure = $erasure;
+        $crate::downcast_match!(@arms erasure; $($arms)*)
+    }};
+    (@arms $erasure:ident; $binding:ident : $typ:ty => $arm:expr, $($rest:tt)*) => {
+        match $crate::erasure::TaggedErasure::<$typ>::try_downcast($erasure) {
+            Ok($binding) => $arm,
+            Err($erasure) => $crate::downcast_match!(@arms $erasure; $($rest)*)
+        }
+    };
+    (@arms $erasure:ident; $binding:ident : $typ:ty => $arm:block $($rest:tt)*) => {
+        $crate::downcast_match!(@arms $erasure; $binding : $typ => $arm, $($rest)*)
+    };
+    (@arms $erasure:ident; $fallback:pat => $arm:expr $(,)?) => {
+        match $erasure {
+            $fallback => $arm
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use crate::erasure::ErasedError;
+
+    #[test]
+    fn owned() {
+        fn classify(erased: Box<dyn Any>) -> String {
+            downcast_match!(erased => {
+                string: String => string,
+                number: u32 => {
+                    number.to_string()
+                }
+                other => format!("other ({})", other.is::<i32>())
+            })
+        }
+
+        assert_eq!(classify(Box::new(String::from("test"))), "test");
+        assert_eq!(classify(Box::new(7u32)), "7");
+        assert_eq!(classify(Box::new(7i32)), "other (true)");
+    }
+
+    #[test]
+    fn by_mut() {
+        let mut value: Box<dyn Any> = Box::new(1u64);
+
+        for _ in 0..2 {
+            downcast_match!(value.as_mut() => {
+                number: &mut u64 => *number += 1,
+                string: &mut String => string.push('!'),
+                _ => unreachable!(),
+            });
+        }
+
+        assert_eq!(value.downcast_ref::<u64>(), Some(&3));
+    }
+
+    #[test]
+    fn erased_error() {
+        let error = ErasedError::new(std::fmt::Error);
+
+        let matched = downcast_match!(error => {
+            _io: std::io::Error => "io",
+            _fmt: std::fmt::Error => "fmt",
+            _ => "other",
+        });
+
+        assert_eq!(matched, "fmt");
+    }
+}
@@ -1,5 +1,8 @@
 use std::any::Any;
 use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
 use crate::erasure::Erasure;
 use crate::for_all_combinations;
 
@@ -31,6 +34,24 @@ macro_rules! impl_erase_for_any {
                 value as &mut (dyn Any$( + $traits)*)
             }
         }
+        
+        impl<T: 'static$( + $traits)*> Erase<Rc<T>> for Rc<dyn Any$( + $traits)*> {
+            fn erase(value: Rc<T>) -> Self {
+                value as Rc<dyn Any$( + $traits)*>
+            }
+        }
+        
+        impl<T: 'static$( + $traits)*> Erase<Arc<T>> for Arc<dyn Any$( + $traits)*> {
+            fn erase(value: Arc<T>) -> Self {
+                value as Arc<dyn Any$( + $traits)*>
+            }
+        }
+        
+        impl<T: 'static$( + $traits)*> Erase<Pin<Box<T>>> for Pin<Box<dyn Any$( + $traits)*>> {
+            fn erase(value: Pin<Box<T>>) -> Self {
+                value as Pin<Box<dyn Any$( + $traits)*>>
+            }
+        }
     };
 }
 
@@ -1,25 +1,76 @@
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+#[cfg(feature = "telemetry")]
+use std::any::type_name;
 use crate::erasure::auto_drop_unique::AutoDropUnique;
-use crate::erasure::{Erase, Erasure};
+use crate::erasure::{AllocError, Erase, Erasure, ErasureMut, ErasureRef, TryErase};
+#[cfg(feature = "telemetry")]
+use crate::erasure::telemetry::{outermost, record, TelemetryEventKind};
 
 /// An owned, type-erased value on the heap, which preserves the lifetime of the value it was
 /// created from.
 /// 
+/// With the `telemetry` feature, the events of the value are recorded for the `ErasedBox`, rather
+/// than for the underlying erasure.
+/// 
 /// Generic Parameters:
 /// - `'lifetime`: the lifetime of the `ErasedBox`, which ties it to the originating value.
 /// - `E`: the inderlying (lifetime-less) [erasure](Erasure).
 pub struct ErasedBox<'lifetime, E: 'lifetime = AutoDropUnique> {
-    pointer: E,
+    /// The underlying erasure (only dropped within [outermost] with the `telemetry` feature).
+    pointer: ManuallyDrop<E>,
     lifetime: PhantomData<&'lifetime ()>,
+    /// The type name and size of the value, for telemetry.
+    #[cfg(feature = "telemetry")]
+    recorded: (&'static str, usize)
 }
 
 impl<'lifetime, E: 'lifetime> ErasedBox<'lifetime, E> {
     /// Takes ownership of `value` and [erases](Erase) its type.
     pub fn new<T: 'lifetime>(value: T) -> Self where E: Erase<T> {
-        Self {
-            pointer: E::erase(value),
-            lifetime: PhantomData
-        }
+        #[cfg(feature = "telemetry")]
+        let pointer = outermost::<E, _>(|| E::erase(value));
+        
+        #[cfg(not(feature = "telemetry"))]
+        let pointer = E::erase(value);
+        
+        Self::from_erasure::<T>(pointer)
+    }
+    
+    /// Takes ownership of `value` and [erases](TryErase) its type, or returns an error (dropping
+    /// the value) if the erasure fails to allocate.
+    pub fn try_new<T: 'lifetime>(value: T) -> Result<Self, AllocError> where E: TryErase<T> {
+        #[cfg(feature = "telemetry")]
+        let pointer = outermost::<E, _>(|| E::try_erase(value));
+        
+        #[cfg(not(feature = "telemetry"))]
+        let pointer = E::try_erase(value);
+        
+        pointer.map(Self::from_erasure::<T>)
+    }
+    
+    /// Wraps the erasure of a `T` (`T` is only needed for telemetry).
+    #[cfg_attr(not(feature = "telemetry"), allow(clippy::extra_unused_type_parameters))]
+    fn from_erasure<T: 'lifetime>(pointer: E) -> Self {
+        let erased = Self {
+            pointer: ManuallyDrop::new(pointer),
+            lifetime: PhantomData,
+            #[cfg(feature = "telemetry")]
+            recorded: (type_name::<T>(), size_of::<T>())
+        };
+        
+        #[cfg(feature = "telemetry")]
+        erased.record(TelemetryEventKind::Erased);
+        
+        erased
+    }
+    
+    /// Records a [telemetry event](crate::erasure::telemetry) for the value.
+    #[cfg(feature = "telemetry")]
+    fn record(&self, kind: TelemetryEventKind) {
+        let (type_name, size) = self.recorded;
+        
+        record::<Self>(kind, "ErasedBox", type_name, size)
     }
 }
 
@@ -29,32 +80,57 @@ impl<
     E: Erasure<T> + 'lifetime
 > Erasure<T> for ErasedBox<'lifetime, E> {
     unsafe fn downcast_unchecked(self) -> T {
-        self.pointer.downcast_unchecked()
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Downcast);
+        
+        // Moving the erasure out, so the box is not dropped
+        let mut this = ManuallyDrop::new(self);
+        
+        let pointer = ManuallyDrop::take(&mut this.pointer);
+        
+        #[cfg(feature = "telemetry")]
+        return outermost::<E, _>(|| pointer.downcast_unchecked());
+        
+        #[cfg(not(feature = "telemetry"))]
+        pointer.downcast_unchecked()
     }
 }
 
-impl<
-    'borrow,
-    'lifetime: 'borrow,
-    T: 'lifetime,
-    E: 'lifetime
-> Erasure<&'borrow T> for &'borrow ErasedBox<'lifetime, E> 
-    where &'borrow E: Erasure<&'borrow T>
-{
-    unsafe fn downcast_unchecked(self) -> &'borrow T {
-        <&'borrow E as Erasure<&'borrow T>>::downcast_unchecked(&self.pointer)
+impl<'lifetime, E: 'lifetime> Drop for ErasedBox<'lifetime, E> {
+    fn drop(&mut self) {
+        // SAFETY: The erasure is only dropped here, and isn't used afterwards.
+        let pointer = unsafe { ManuallyDrop::take(&mut self.pointer) };
+        
+        #[cfg(feature = "telemetry")]
+        {
+            self.record(TelemetryEventKind::Dropped);
+            
+            outermost::<E, _>(|| drop(pointer));
+        }
+        
+        #[cfg(not(feature = "telemetry"))]
+        drop(pointer);
     }
 }
 
 impl<
-    'borrow,
-    'lifetime: 'borrow,
+    'lifetime,
     T: 'lifetime,
-    E: 'lifetime 
-> Erasure<&'borrow mut T> for &'borrow mut ErasedBox<'lifetime, E> 
-    where &'borrow mut E: Erasure<&'borrow mut T>
-{
-    unsafe fn downcast_unchecked(self) -> &'borrow mut T {
-        <&'borrow mut E as Erasure<&'borrow mut T>>::downcast_unchecked(&mut self.pointer)
+    E: TryErase<T> + 'lifetime
+> TryErase<T> for ErasedBox<'lifetime, E> {
+    fn try_erase(value: T) -> Result<Self, AllocError> {
+        Self::try_new(value)
+    }
+}
+
+impl<'lifetime, E: ErasureRef + 'lifetime> ErasureRef for ErasedBox<'lifetime, E> {
+    unsafe fn downcast_ref_unchecked<T>(&self) -> &T {
+        self.pointer.downcast_ref_unchecked()
+    }
+}
+
+impl<'lifetime, E: ErasureMut + 'lifetime> ErasureMut for ErasedBox<'lifetime, E> {
+    unsafe fn downcast_mut_unchecked<T>(&mut self) -> &mut T {
+        self.pointer.downcast_mut_unchecked()
     }
 }
@@ -0,0 +1,550 @@
+use std::alloc::handle_alloc_error;
+use std::any::{type_name, TypeId};
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::mem::{forget, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::ptr::{addr_of_mut, NonNull};
+use crate::erasure::{
+    defer_downcast_unchecked_to_try_downcast, AllocError, Erase, Erasure, TaggedErasure, TryErase
+};
+use crate::util::{try_box_uninit, CPair};
+
+/// An owning pointer to a type-erased [Error] on the heap.
+///
+/// A single-word alternative to `Box<dyn Error + Send + Sync>`. The error is kept on the heap
+/// alongside a header containing its vtable, its type, a chain of [context](ErasedError::context)
+/// messages, and the [Backtrace] captured when the `ErasedError` was created.
+///
+/// Dereferences to the underlying error, and is a [tagged erasure](TaggedErasure) of it, so the
+/// concrete error type can be recovered with [Is](crate::erasure::Is) or
+/// [try_downcast](TaggedErasure::try_downcast).
+pub struct ErasedError {
+    /// Pointer to the header/error pair on the heap (see [ErasedErrorInner]).
+    inner: NonNull<ErasedErrorHeader>
+}
+
+// SAFETY: Errors are required to be `Send + Sync` on construction, as is all context.
+unsafe impl Send for ErasedError {}
+unsafe impl Sync for ErasedError {}
+
+impl ErasedError {
+    /// Takes ownership of the given error, erasing its type.
+    ///
+    /// A [Backtrace] is captured if enabled (see [Backtrace::capture]).
+    pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        Self::try_new(error).unwrap_or_else(|error| handle_alloc_error(error.layout()))
+    }
+
+    /// Takes ownership of the given error, as [ErasedError::new], but returns an error (dropping
+    /// the given error) instead of aborting if the allocation fails.
+    ///
+    /// E.g. with an [AlignedArena](crate::align::AlignedArena) as the global allocator:
+    /// ```
+    /// use std::fmt::{Display, Formatter};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use dynrsaur::align::AlignedArena;
+    /// use dynrsaur::erasure::ErasedError;
+    ///
+    /// #[global_allocator]
+    /// static ARENA: AlignedArena<{ 1 << 16 }, 4096> = AlignedArena::new();
+    ///
+    /// static DROPPED: AtomicBool = AtomicBool::new(false);
+    ///
+    /// #[derive(Debug)]
+    /// struct HugeError([u8; 1 << 16]);
+    ///
+    /// impl Display for HugeError {
+    ///     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    ///         write!(f, "huge error")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for HugeError {}
+    ///
+    /// impl Drop for HugeError {
+    ///     fn drop(&mut self) {
+    ///         DROPPED.store(true, Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// assert!(ErasedError::try_new(HugeError([0; 1 << 16])).is_err());
+    /// assert!(DROPPED.load(Ordering::Relaxed));
+    /// ```
+    pub fn try_new<E: Error + Send + Sync + 'static>(error: E) -> Result<Self, AllocError> {
+        // Allocated before the error is moved in, so that it is dropped if the allocation fails
+        let mut inner = try_box_uninit::<ErasedErrorInner<E>>()?;
+
+        inner.write(
+            CPair(
+                ErasedErrorHeader {
+                    // Placeholder until the error has its final address
+                    error: NonNull::<E>::dangling(),
+                    type_id: TypeId::of::<E>(),
+                    type_name: type_name::<E>(),
+                    drop: drop_impl::<E>,
+                    context: Vec::new(),
+                    backtrace: Some(Backtrace::capture())
+                        .filter(|backtrace| backtrace.status() == BacktraceStatus::Captured)
+                },
+                ManuallyDrop::new(error)
+            )
+        );
+
+        let inner_ptr = Box::into_raw(inner) as *mut ErasedErrorInner<E>;
+
+        // SAFETY: `inner_ptr` is from a Box (so valid and initialised), and the error is within
+        //         the same allocation.
+        unsafe {
+            let error = addr_of_mut!((*inner_ptr).1) as *mut E;
+            (*inner_ptr).0.error = NonNull::new_unchecked(error);
+
+            Ok(ErasedError {
+                inner: NonNull::new_unchecked(inner_ptr).cast()
+            })
+        }
+    }
+
+    /// Wraps the error with an additional layer of context.
+    pub fn context<C: Display + Send + Sync + 'static>(mut self, context: C) -> Self {
+        self.header_mut().context.push(Box::new(context));
+        self
+    }
+
+    /// Iterates over the context of the error, from the outermost (most recently added) inwards.
+    pub fn contexts(&self) -> impl Iterator<Item = &(dyn Display + Send + Sync)> {
+        self.header().context.iter().rev().map(Box::deref)
+    }
+
+    /// The backtrace captured when the error was erased, if backtraces were enabled.
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.header().backtrace.as_ref()
+    }
+
+    /// The name of the underlying error type.
+    pub fn type_name(&self) -> &'static str {
+        self.header().type_name
+    }
+
+    /// Borrows the underlying error.
+    pub fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        // SAFETY: `error` points to the error in the same allocation as the header.
+        unsafe { self.header().error.as_ref() }
+    }
+
+    /// Mutably borrows the underlying error.
+    pub fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static) {
+        // SAFETY: `error` points to the error in the same allocation as the header.
+        unsafe { self.header_mut().error.as_mut() }
+    }
+
+    /// Checks if the underlying error is an `E`.
+    fn is_type<E: 'static>(&self) -> bool {
+        self.header().type_id == TypeId::of::<E>()
+    }
+
+    fn header(&self) -> &ErasedErrorHeader {
+        // SAFETY: The header is at the start of the `#[repr(C)]` pair.
+        unsafe { self.inner.as_ref() }
+    }
+
+    fn header_mut(&mut self) -> &mut ErasedErrorHeader {
+        // SAFETY: The header is at the start of the `#[repr(C)]` pair.
+        unsafe { self.inner.as_mut() }
+    }
+
+    /// Returns a pointer to the error, assuming it is an `E`.
+    ///
+    /// # Safety
+    ///
+    /// The erased error must be an `E`.
+    unsafe fn as_ptr<E>(&self) -> *mut E {
+        let inner_ptr = self.inner.cast::<ErasedErrorInner<E>>().as_ptr();
+        addr_of_mut!((*inner_ptr).1) as *mut E
+    }
+}
+
+impl Deref for ErasedError {
+    type Target = dyn Error + Send + Sync + 'static;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_error()
+    }
+}
+
+impl DerefMut for ErasedError {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_error_mut()
+    }
+}
+
+impl AsRef<dyn Error + Send + Sync> for ErasedError {
+    fn as_ref(&self) -> &(dyn Error + Send + Sync + 'static) {
+        self.as_error()
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> From<E> for ErasedError {
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl From<ErasedError> for Box<dyn Error + Send + Sync> {
+    fn from(error: ErasedError) -> Self {
+        Box::new(ErasedErrorAdapter(error))
+    }
+}
+
+impl Display for ErasedError {
+    /// Writes the outermost context, or the error if there is none.
+    ///
+    /// The alternate form (`{:#}`) writes all context followed by the error, separated by `: `.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            for context in self.contexts() {
+                write!(f, "{}: ", context)?;
+            }
+            Display::fmt(self.as_error(), f)
+        } else {
+            match self.contexts().next() {
+                Some(context) => Display::fmt(context, f),
+                None => Display::fmt(self.as_error(), f)
+            }
+        }
+    }
+}
+
+impl Debug for ErasedError {
+    /// Writes the outermost context (or error), followed by its causes and backtrace.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut causes = self.contexts()
+            .map(|context| context.to_string())
+            .chain(Some(self.as_error().to_string()))
+            .chain(ErrorSources(self.as_error().source()).map(|source| source.to_string()));
+
+        if let Some(outermost) = causes.next() {
+            write!(f, "{}", outermost)?;
+        }
+
+        let mut causes = causes.peekable();
+
+        if causes.peek().is_some() {
+            write!(f, "\n\nCaused by:")?;
+            for (index, cause) in causes.enumerate() {
+                write!(f, "\n    {}: {}", index, cause)?;
+            }
+        }
+
+        write!(f, "\n\nError type: {}", self.type_name())?;
+
+        if let Some(backtrace) = self.backtrace() {
+            write!(f, "\n\nStack backtrace:\n{}", backtrace)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for ErasedError {
+    fn drop(&mut self) {
+        let drop = self.header().drop;
+
+        // SAFETY: `drop` was created for the erased error type, and is only called once.
+        unsafe { drop(self.inner.cast()) }
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> Erasure<E> for ErasedError {
+    unsafe fn downcast_unchecked(self) -> E {
+        defer_downcast_unchecked_to_try_downcast(self)
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> TaggedErasure<E> for ErasedError {
+    fn is(&self) -> bool {
+        self.is_type::<E>()
+    }
+
+    fn underlying_type_name(&self) -> Option<&'static str> {
+        Some(self.type_name())
+    }
+
+    fn try_downcast(self) -> Result<E, Self> {
+        if !self.is_type::<E>() {
+            return Err(self)
+        }
+
+        let inner = self.inner.cast::<ErasedErrorInner<E>>();
+
+        forget(self);
+
+        // SAFETY: created by Box::into_raw, and checked to be an `E` above.
+        let CPair(_header, error) = *unsafe { Box::from_raw(inner.as_ptr()) };
+
+        Ok(ManuallyDrop::into_inner(error))
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> Erase<E> for ErasedError {
+    fn erase(value: E) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> TryErase<E> for ErasedError {
+    fn try_erase(value: E) -> Result<Self, AllocError> {
+        Self::try_new(value)
+    }
+}
+
+impl<'borrow, E: Error + Send + Sync + 'static> Erasure<&'borrow E> for &'borrow ErasedError {
+    unsafe fn downcast_unchecked(self) -> &'borrow E {
+        defer_downcast_unchecked_to_try_downcast(self)
+    }
+}
+
+impl<'borrow, E: Error + Send + Sync + 'static> TaggedErasure<&'borrow E> for &'borrow ErasedError {
+    fn is(&self) -> bool {
+        self.is_type::<E>()
+    }
+
+    fn underlying_type_name(&self) -> Option<&'static str> {
+        Some(self.type_name())
+    }
+
+    fn try_downcast(self) -> Result<&'borrow E, Self> {
+        if self.is_type::<E>() {
+            // SAFETY: Checked to be an `E` above.
+            Ok(unsafe { &*self.as_ptr::<E>() })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<'borrow, E: Error + Send + Sync + 'static> Erasure<&'borrow mut E> for &'borrow mut ErasedError {
+    unsafe fn downcast_unchecked(self) -> &'borrow mut E {
+        defer_downcast_unchecked_to_try_downcast(self)
+    }
+}
+
+impl<'borrow, E: Error + Send + Sync + 'static> TaggedErasure<&'borrow mut E> for &'borrow mut ErasedError {
+    fn is(&self) -> bool {
+        self.is_type::<E>()
+    }
+
+    fn underlying_type_name(&self) -> Option<&'static str> {
+        Some(self.type_name())
+    }
+
+    fn try_downcast(self) -> Result<&'borrow mut E, Self> {
+        if self.is_type::<E>() {
+            // SAFETY: Checked to be an `E` above.
+            Ok(unsafe { &mut *self.as_ptr::<E>() })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// The information about an [ErasedError] stored alongside the error on the heap.
+struct ErasedErrorHeader {
+    /// Pointer to the error in the same allocation, holding its vtable.
+    error: NonNull<dyn Error + Send + Sync>,
+    /// The type of the error.
+    type_id: TypeId,
+    /// The name of the type of the error.
+    type_name: &'static str,
+    /// Pointer to [drop_impl].
+    drop: unsafe fn(NonNull<()>),
+    /// The context the error has been wrapped in, from the innermost outwards.
+    context: Vec<Box<dyn Display + Send + Sync>>,
+    /// The backtrace captured on creation, if enabled.
+    backtrace: Option<Backtrace>
+}
+
+/// The heap data-structure held by [ErasedError].
+///
+/// A C-like pair of:
+/// 1. the [header](ErasedErrorHeader) for the error, and,
+/// 2. the error itself.
+type ErasedErrorInner<E> = CPair<ErasedErrorHeader, ManuallyDrop<E>>;
+
+/// Implementation of drop which remembers the type of the error.
+///
+/// # Safety
+///
+/// Must only be called when dropping the [ErasedError], and `inner` must be the
+/// [ErasedErrorInner] of an `E`.
+unsafe fn drop_impl<E>(inner: NonNull<()>) {
+    let mut ptr = inner.cast::<ErasedErrorInner<E>>();
+
+    // Extract the error from the inner pair and drop it
+    ManuallyDrop::drop(&mut ptr.as_mut().1);
+
+    // Drop the header and the heap memory
+    drop(Box::from_raw(ptr.as_ptr()))
+}
+
+/// Iterator over the [sources](Error::source) of an error.
+struct ErrorSources<'error>(Option<&'error (dyn Error + 'static)>);
+
+impl<'error> Iterator for ErrorSources<'error> {
+    type Item = &'error (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.0?;
+        self.0 = current.source();
+        Some(current)
+    }
+}
+
+/// Implements [Error] for an [ErasedError], so it can be converted into a `Box<dyn Error>`.
+///
+/// `ErasedError` can't implement `Error` itself, as that would conflict with its blanket
+/// [From] implementation.
+struct ErasedErrorAdapter(ErasedError);
+
+impl Debug for ErasedErrorAdapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for ErasedErrorAdapter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for ErasedErrorAdapter {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.0.contexts().next() {
+            Some(_) => Some(self.0.as_error()),
+            None => self.0.as_error().source()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::fmt::{Display, Formatter};
+    use std::io;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use crate::erasure::{ErasedError, Is, TaggedErasure};
+
+    #[derive(Debug, PartialEq)]
+    struct TestError(u32);
+
+    impl Display for TestError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "test error {}", self.0)
+        }
+    }
+
+    impl Error for TestError {}
+
+    #[test]
+    fn single_word_size() {
+        assert_eq!(size_of::<ErasedError>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<ErasedError>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn display_and_context() {
+        let error = ErasedError::new(TestError(7));
+
+        assert_eq!(error.to_string(), "test error 7");
+
+        let error = error.context("reading config").context("starting up");
+
+        assert_eq!(error.to_string(), "starting up");
+        assert_eq!(format!("{:#}", error), "starting up: reading config: test error 7");
+        assert_eq!(
+            error.contexts().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["starting up", "reading config"]
+        );
+    }
+
+    #[test]
+    fn question_mark() {
+        fn fails() -> Result<(), ErasedError> {
+            Err(io::Error::other("io failure"))?;
+            Ok(())
+        }
+
+        let error = fails().unwrap_err();
+
+        assert_eq!(error.to_string(), "io failure");
+        assert!(TaggedErasure::<io::Error>::is(&error));
+    }
+
+    #[test]
+    fn downcast() {
+        let error = ErasedError::new(TestError(3)).context("context");
+
+        let error = match TaggedErasure::<io::Error>::try_downcast(error) {
+            Ok(_) => panic!("not an io::Error"),
+            Err(error) => error
+        };
+
+        let is = Is::<TestError, _>::new(error).expect("is a TestError");
+
+        assert_eq!(is.downcast(), TestError(3));
+    }
+
+    #[test]
+    fn downcast_ref() {
+        let mut error = ErasedError::new(TestError(3));
+
+        let test_error: &mut TestError = TaggedErasure::try_downcast(&mut error).ok().unwrap();
+        test_error.0 = 4;
+
+        let test_error: &TestError = TaggedErasure::try_downcast(&error).ok().unwrap();
+        assert_eq!(test_error, &TestError(4));
+        assert_eq!(error.type_name(), std::any::type_name::<TestError>());
+    }
+
+    #[test]
+    fn into_boxed_error() {
+        let error = ErasedError::new(TestError(1)).context("outer");
+
+        let boxed: Box<dyn Error + Send + Sync> = error.into();
+
+        assert_eq!(boxed.to_string(), "outer");
+        assert_eq!(boxed.source().unwrap().to_string(), "test error 1");
+    }
+
+    #[test]
+    fn erased_drop() {
+        static DROPPED: AtomicBool = AtomicBool::new(false);
+
+        #[derive(Debug)]
+        struct DropError;
+
+        impl Display for DropError {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str("drop error")
+            }
+        }
+
+        impl Error for DropError {}
+
+        impl Drop for DropError {
+            fn drop(&mut self) {
+                DROPPED.store(true, Ordering::Release);
+            }
+        }
+
+        {
+            let _erased = ErasedError::new(DropError).context("context");
+
+            assert!(!DROPPED.load(Ordering::Acquire));
+        }
+
+        assert!(DROPPED.load(Ordering::Acquire));
+    }
+}
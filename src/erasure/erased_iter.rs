@@ -0,0 +1,315 @@
+use std::marker::PhantomData;
+use std::mem::{forget, ManuallyDrop};
+use std::ptr::{addr_of_mut, NonNull};
+use std::alloc::handle_alloc_error;
+use crate::erasure::{AllocError, Erase, Erasure, ErasureMut, ErasureRef, TryErase};
+use crate::util::{try_box_uninit, CPair};
+#[cfg(feature = "verify-types")]
+use std::any::TypeId;
+#[cfg(feature = "verify-types")]
+use crate::erasure::type_verification::{type_info, verify_type_info};
+
+/// An owning pointer to a type-erased [Iterator] on the heap.
+///
+/// Like [AutoDropUnique](crate::erasure::AutoDropUnique), the pointer is a single word, and the
+/// functions which depend on the concrete iterator type (`next`, `size_hint` and `drop`) are kept
+/// in a header alongside the iterator on the heap. Unlike `Box<dyn Iterator<Item = Item>>`, the
+/// concrete iterator can be recovered with [Erasure::downcast_unchecked].
+///
+/// The iterator may borrow data for `'lifetime`, so the erasure can't outlive that data:
+/// ```compile_fail
+/// use dynrsaur::erasure::ErasedIter;
+///
+/// let erased = {
+///     let values = vec![1, 2, 3];
+///     ErasedIter::new(values.iter().copied())
+/// };
+///
+/// assert_eq!(erased.sum::<i32>(), 6);
+/// ```
+///
+/// Generic Parameters:
+/// - `'lifetime`: the lifetime of any data borrowed by the iterator.
+/// - `Item`: the type of the items yielded by the iterator.
+pub struct ErasedIter<'lifetime, Item> {
+    /// Pointer to the header/iterator pair on the heap (see [ErasedIterInner]).
+    inner: NonNull<ErasedIterHeader<Item>>,
+    /// Marker indicating that we yield `Item`s, and may borrow data for `'lifetime`.
+    _marker: PhantomData<(&'lifetime (), Item)>
+}
+
+impl<'lifetime, Item> ErasedIter<'lifetime, Item> {
+    /// Takes ownership of the given iterator, erasing its type.
+    ///
+    /// This always allocates, as the header must be stored even if the iterator is a ZST.
+    pub fn new<I: Iterator<Item = Item> + 'lifetime>(iter: I) -> Self {
+        Self::try_new(iter).unwrap_or_else(|error| handle_alloc_error(error.layout()))
+    }
+
+    /// Takes ownership of the given iterator, as [ErasedIter::new], but returns an error (dropping
+    /// the iterator) instead of aborting if the allocation fails.
+    pub fn try_new<I: Iterator<Item = Item> + 'lifetime>(iter: I) -> Result<Self, AllocError> {
+        // Allocated before the iterator is moved in, so that it is dropped if the allocation fails
+        let mut inner = try_box_uninit::<ErasedIterInner<I>>()?;
+
+        inner.write(
+            CPair(
+                ErasedIterHeader {
+                    next: next_impl::<I>,
+                    size_hint: size_hint_impl::<I>,
+                    drop: drop_impl::<I>,
+                    #[cfg(feature = "verify-types")]
+                    type_info: type_info::<I>()
+                },
+                ManuallyDrop::new(iter)
+            )
+        );
+
+        Ok(ErasedIter {
+            // SAFETY: From a Box, so non-null (and initialised above).
+            inner: unsafe { NonNull::new_unchecked(Box::into_raw(inner)).cast() },
+            _marker: PhantomData
+        })
+    }
+
+    /// Returns the header of the erased iterator.
+    fn header(&self) -> &ErasedIterHeader<Item> {
+        // SAFETY: The header is at the start of the `#[repr(C)]` pair.
+        unsafe { self.inner.as_ref() }
+    }
+
+    /// Panics if the erased iterator is not an `I`.
+    #[cfg(feature = "verify-types")]
+    fn verify_type<I>(&self) {
+        verify_type_info::<I>(self.header().type_info)
+    }
+
+    /// Returns a pointer to the iterator, assuming it is an `I`.
+    ///
+    /// # Safety
+    ///
+    /// The erased iterator must be an `I`.
+    unsafe fn as_ptr<I>(&self) -> *mut I {
+        let inner_ptr = self.inner.cast::<CPair<ErasedIterHeader<Item>, ManuallyDrop<I>>>().as_ptr();
+        addr_of_mut!((*inner_ptr).1) as *mut I
+    }
+}
+
+impl<Item> Iterator for ErasedIter<'_, Item> {
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: `next` was created for the erased iterator type, and we hold `&mut self`.
+        unsafe { (self.header().next)(self.inner.cast()) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // SAFETY: `size_hint` was created for the erased iterator type.
+        unsafe { (self.header().size_hint)(self.inner.cast()) }
+    }
+}
+
+impl<Item> Drop for ErasedIter<'_, Item> {
+    fn drop(&mut self) {
+        // SAFETY: `drop` was created for the erased iterator type, and is only called once.
+        unsafe { (self.header().drop)(self.inner.cast()) }
+    }
+}
+
+impl<'lifetime, I: Iterator + 'lifetime> Erasure<I> for ErasedIter<'lifetime, I::Item> {
+    unsafe fn downcast_unchecked(self) -> I {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<I>();
+
+        let inner = self.inner.cast::<ErasedIterInner<I>>();
+
+        forget(self);
+
+        // SAFETY: created by Box::into_raw
+        let boxed = Box::from_raw(inner.as_ptr());
+
+        ManuallyDrop::into_inner((*boxed).1)
+    }
+}
+
+impl<'lifetime, I: Iterator + 'lifetime> Erase<I> for ErasedIter<'lifetime, I::Item> {
+    fn erase(value: I) -> Self {
+        ErasedIter::new(value)
+    }
+}
+
+impl<'lifetime, I: Iterator + 'lifetime> TryErase<I> for ErasedIter<'lifetime, I::Item> {
+    fn try_erase(value: I) -> Result<Self, AllocError> {
+        ErasedIter::try_new(value)
+    }
+}
+
+impl<Item> ErasureRef for ErasedIter<'_, Item> {
+    unsafe fn downcast_ref_unchecked<I>(&self) -> &I {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<I>();
+
+        &*self.as_ptr::<I>()
+    }
+}
+
+impl<Item> ErasureMut for ErasedIter<'_, Item> {
+    unsafe fn downcast_mut_unchecked<I>(&mut self) -> &mut I {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<I>();
+
+        &mut *self.as_ptr::<I>()
+    }
+}
+
+/// The functions of an [ErasedIter] that depend on the concrete iterator type.
+///
+/// Each function takes a pointer to the [ErasedIterInner] of the iterator, [cast to unit](NonNull::cast).
+struct ErasedIterHeader<Item> {
+    /// Pointer to [next_impl].
+    next: unsafe fn(NonNull<()>) -> Option<Item>,
+    /// Pointer to [size_hint_impl].
+    size_hint: unsafe fn(NonNull<()>) -> (usize, Option<usize>),
+    /// Pointer to [drop_impl].
+    drop: unsafe fn(NonNull<()>),
+    /// The [type information](type_info) of the iterator, to verify downcasts.
+    #[cfg(feature = "verify-types")]
+    type_info: (TypeId, &'static str)
+}
+
+/// The heap data-structure held by [ErasedIter].
+///
+/// A C-like pair of:
+/// 1. the [header](ErasedIterHeader) of functions for the iterator, and,
+/// 2. the iterator itself.
+type ErasedIterInner<I> = CPair<
+    ErasedIterHeader<<I as Iterator>::Item>,
+    ManuallyDrop<I>
+>;
+
+/// Implementation of [Iterator::next] which remembers the type of the iterator.
+///
+/// # Safety
+///
+/// `inner` must be the [ErasedIterInner] of an `I`, and must be uniquely borrowed.
+unsafe fn next_impl<I: Iterator>(inner: NonNull<()>) -> Option<I::Item> {
+    let mut ptr = inner.cast::<ErasedIterInner<I>>();
+
+    ptr.as_mut().1.next()
+}
+
+/// Implementation of [Iterator::size_hint] which remembers the type of the iterator.
+///
+/// # Safety
+///
+/// `inner` must be the [ErasedIterInner] of an `I`.
+unsafe fn size_hint_impl<I: Iterator>(inner: NonNull<()>) -> (usize, Option<usize>) {
+    let ptr = inner.cast::<ErasedIterInner<I>>();
+
+    ptr.as_ref().1.size_hint()
+}
+
+/// Implementation of drop which remembers the type of the iterator.
+///
+/// # Safety
+///
+/// Must only be called when dropping the [ErasedIter], and `inner` must be the
+/// [ErasedIterInner] of an `I`.
+unsafe fn drop_impl<I: Iterator>(inner: NonNull<()>) {
+    let mut ptr = inner.cast::<ErasedIterInner<I>>();
+
+    // Extract the iterator from the inner pair and drop it
+    ManuallyDrop::drop(&mut ptr.as_mut().1);
+
+    // Drop the heap memory
+    drop(Box::from_raw(ptr.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use crate::erasure::{Erase, ErasedIter, Erasure};
+
+    #[test]
+    fn single_word_size() {
+        assert_eq!(size_of::<ErasedIter<'_, u8>>(), size_of::<usize>());
+        assert_eq!(size_of::<ErasedIter<'_, String>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn iterates() {
+        let erased = ErasedIter::new(vec![1, 2, 3].into_iter().map(|x| x * 2));
+
+        assert_eq!(erased.collect::<Vec<_>>(), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut erased = ErasedIter::new(0..5);
+
+        assert_eq!(erased.size_hint(), (5, Some(5)));
+
+        erased.next();
+
+        assert_eq!(erased.size_hint(), (4, Some(4)));
+    }
+
+    #[test]
+    fn zst_iterator() {
+        let erased = ErasedIter::new(std::iter::empty::<String>());
+
+        assert_eq!(erased.size_hint(), (0, Some(0)));
+        assert_eq!(erased.count(), 0);
+    }
+
+    #[test]
+    fn readback() {
+        let mut erased: ErasedIter<'_, usize> = Erase::erase(10..20);
+
+        erased.next();
+
+        let unerased: std::ops::Range<usize> = unsafe { erased.downcast_unchecked() };
+
+        assert_eq!(unerased, 11..20);
+    }
+
+    #[test]
+    fn readback_ref() {
+        let mut erased = ErasedIter::new(10..20usize);
+
+        let unerased: &mut std::ops::Range<usize> = unsafe { (&mut erased).downcast_unchecked() };
+
+        unerased.start = 15;
+
+        assert_eq!(erased.next(), Some(15));
+    }
+
+    #[test]
+    fn erased_drop() {
+        let dropped = Cell::new(false);
+
+        struct SetTrueOnDrop<'a>(&'a Cell<bool>);
+
+        impl Iterator for SetTrueOnDrop<'_> {
+            type Item = ();
+
+            fn next(&mut self) -> Option<Self::Item> {
+                None
+            }
+        }
+
+        impl Drop for SetTrueOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        {
+            let _erased = ErasedIter::new(SetTrueOnDrop(&dropped));
+
+            assert!(!dropped.get());
+        }
+
+        assert!(dropped.get());
+    }
+}
@@ -0,0 +1,142 @@
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use crate::erasure::{Erase, Erasure};
+
+/// A type-erased shared reference, which preserves the lifetime of the reference it was created
+/// from.
+///
+/// Unlike `&dyn Unknown`, which carries a (methodless) vtable, this is a single word in size.
+///
+/// Generic Parameters:
+/// - `'lifetime`: the lifetime of the original reference.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct ErasedRef<'lifetime> {
+    /// The address of the referenced value.
+    pointer: NonNull<()>,
+    /// Marker indicating that we borrow a value for `'lifetime`.
+    _lifetime: PhantomData<&'lifetime ()>
+}
+
+impl<'lifetime> ErasedRef<'lifetime> {
+    /// Erases the type of the given reference.
+    pub fn new<T>(reference: &'lifetime T) -> Self {
+        ErasedRef {
+            pointer: NonNull::from(reference).cast(),
+            _lifetime: PhantomData
+        }
+    }
+}
+
+impl<'lifetime, T: 'lifetime> Erasure<&'lifetime T> for ErasedRef<'lifetime> {
+    unsafe fn downcast_unchecked(self) -> &'lifetime T {
+        self.pointer.cast::<T>().as_ref()
+    }
+}
+
+impl<'lifetime, T: 'lifetime> Erase<&'lifetime T> for ErasedRef<'lifetime> {
+    fn erase(value: &'lifetime T) -> Self {
+        ErasedRef::new(value)
+    }
+}
+
+impl<'lifetime> From<ErasedMut<'lifetime>> for ErasedRef<'lifetime> {
+    fn from(value: ErasedMut<'lifetime>) -> Self {
+        ErasedRef {
+            pointer: value.pointer,
+            _lifetime: PhantomData
+        }
+    }
+}
+
+/// A type-erased mutable reference, which preserves the lifetime of the reference it was created
+/// from.
+///
+/// Unlike `&mut dyn Unknown`, which carries a (methodless) vtable, this is a single word in size.
+///
+/// Generic Parameters:
+/// - `'lifetime`: the lifetime of the original reference.
+#[repr(transparent)]
+pub struct ErasedMut<'lifetime> {
+    /// The address of the referenced value.
+    pointer: NonNull<()>,
+    /// Marker indicating that we uniquely borrow a value for `'lifetime`.
+    _lifetime: PhantomData<&'lifetime mut ()>
+}
+
+impl<'lifetime> ErasedMut<'lifetime> {
+    /// Erases the type of the given mutable reference.
+    pub fn new<T>(reference: &'lifetime mut T) -> Self {
+        ErasedMut {
+            pointer: NonNull::from(reference).cast(),
+            _lifetime: PhantomData
+        }
+    }
+
+    /// Reborrows the erased reference for a shorter lifetime.
+    pub fn reborrow(&mut self) -> ErasedMut<'_> {
+        ErasedMut {
+            pointer: self.pointer,
+            _lifetime: PhantomData
+        }
+    }
+
+    /// Borrows the erased reference as an [erased shared reference](ErasedRef).
+    pub fn as_ref(&self) -> ErasedRef<'_> {
+        ErasedRef {
+            pointer: self.pointer,
+            _lifetime: PhantomData
+        }
+    }
+}
+
+impl<'lifetime, T: 'lifetime> Erasure<&'lifetime mut T> for ErasedMut<'lifetime> {
+    unsafe fn downcast_unchecked(self) -> &'lifetime mut T {
+        self.pointer.cast::<T>().as_mut()
+    }
+}
+
+impl<'lifetime, T: 'lifetime> Erase<&'lifetime mut T> for ErasedMut<'lifetime> {
+    fn erase(value: &'lifetime mut T) -> Self {
+        ErasedMut::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::erasure::{Erase, ErasedMut, ErasedRef, Erasure};
+
+    #[test]
+    fn single_word_size() {
+        assert_eq!(size_of::<ErasedRef>(), size_of::<usize>());
+        assert_eq!(size_of::<ErasedMut>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<ErasedRef>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn readback() {
+        let string = String::from("test string");
+
+        let erased: ErasedRef = Erase::erase(&string);
+
+        let unerased: &String = unsafe { erased.downcast_unchecked() };
+
+        assert_eq!(unerased, "test string");
+    }
+
+    #[test]
+    fn readback_mut() {
+        let mut value = 1u64;
+
+        let mut erased = ErasedMut::new(&mut value);
+
+        let unerased: &mut u64 = unsafe { erased.reborrow().downcast_unchecked() };
+        *unerased += 1;
+
+        let unerased: &u64 = unsafe { erased.as_ref().downcast_unchecked() };
+        assert_eq!(*unerased, 2);
+
+        let unerased: &u64 = unsafe { ErasedRef::from(erased).downcast_unchecked() };
+        assert_eq!(*unerased, 2);
+    }
+}
@@ -1,5 +1,8 @@
 use std::any::Any;
 use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
 use crate::erasure::defer_downcast_unchecked_to_try_downcast;
 use crate::for_all_combinations;
 
@@ -33,6 +36,24 @@ macro_rules! impl_erasure_for_any {
                 defer_downcast_unchecked_to_try_downcast(self)
             }
         }
+        
+        impl<T: 'static$( + $traits)*> Erasure<Rc<T>> for Rc<dyn Any$( + $traits)*> {
+            unsafe fn downcast_unchecked(self) -> Rc<T> {
+                defer_downcast_unchecked_to_try_downcast(self)
+            }
+        }
+        
+        impl<T: 'static$( + $traits)*> Erasure<Arc<T>> for Arc<dyn Any$( + $traits)*> {
+            unsafe fn downcast_unchecked(self) -> Arc<T> {
+                defer_downcast_unchecked_to_try_downcast(self)
+            }
+        }
+        
+        impl<T: 'static$( + $traits)*> Erasure<Pin<Box<T>>> for Pin<Box<dyn Any$( + $traits)*>> {
+            unsafe fn downcast_unchecked(self) -> Pin<Box<T>> {
+                defer_downcast_unchecked_to_try_downcast(self)
+            }
+        }
     };
 }
 
@@ -0,0 +1,55 @@
+use crate::erasure::{Erasure, ErasureRef};
+
+/// Trait implemented by [erasures](Erasure) whose underlying value can be borrowed through a
+/// mutable reference to the erasure.
+/// 
+/// Implementing this trait provides `Erasure<&mut T> for &mut Self` (for all `T`), so erasure
+/// types don't need to repeat that implementation themselves.
+pub trait ErasureMut: ErasureRef {
+    /// Mutably borrows the underlying value of this erasure as a `T`.
+    /// 
+    /// # Safety
+    /// 
+    /// Caller must ensure that the erasure really is a `T` underneath.
+    unsafe fn downcast_mut_unchecked<T>(&mut self) -> &mut T;
+}
+
+impl<'borrow, T, E: ErasureMut + ?Sized> Erasure<&'borrow mut T> for &'borrow mut E {
+    unsafe fn downcast_unchecked(self) -> &'borrow mut T {
+        self.downcast_mut_unchecked()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::erasure::{ErasedBox, Erasure, ErasureMut, ErasureRef, InlineErasure};
+
+    #[test]
+    fn forwarded_through_erased_box() {
+        let mut string = String::from("test");
+
+        let mut erased: ErasedBox<'_, InlineErasure<8, 8>> = ErasedBox::new(&mut string);
+
+        let unerased: &mut &mut String = unsafe { (&mut erased).downcast_unchecked() };
+        unerased.push_str("ed");
+
+        let unerased: &&mut String = unsafe { erased.downcast_ref_unchecked() };
+        assert_eq!(*unerased, "tested");
+    }
+
+    #[test]
+    fn generic_over_erasure() {
+        fn increment<E: ErasureMut>(erasure: &mut E) {
+            // SAFETY: Only called with erasures of `u32`s.
+            unsafe { *erasure.downcast_mut_unchecked::<u32>() += 1 }
+        }
+
+        let mut erased: ErasedBox = ErasedBox::new(1u32);
+
+        increment(&mut erased);
+        increment(&mut erased);
+
+        let unerased: u32 = unsafe { erased.downcast_unchecked() };
+        assert_eq!(unerased, 3);
+    }
+}
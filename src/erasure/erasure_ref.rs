@@ -0,0 +1,31 @@
+use crate::erasure::Erasure;
+
+/// Trait implemented by [erasures](Erasure) whose underlying value can be borrowed through a
+/// shared reference to the erasure.
+/// 
+/// Implementing this trait provides `Erasure<&T> for &Self` (for all `T`), so erasure types don't
+/// need to repeat that implementation themselves, and wrappers (e.g. [ErasedBox]) can forward it
+/// with a single bound.
+/// 
+/// The owned downcast (`Erasure<T> for Self`) is still implemented separately. A blanket
+/// `Erasure<T>` for all implementors of one trait would overlap with the blanket
+/// `Erasure<&T> for &E` above (as another crate could implement that trait for a reference), so
+/// an erasure type implements [Erasure] for itself, and this trait (and [ErasureMut]) for its
+/// borrows.
+/// 
+/// [ErasedBox]: crate::erasure::ErasedBox
+/// [ErasureMut]: crate::erasure::ErasureMut
+pub trait ErasureRef {
+    /// Borrows the underlying value of this erasure as a `T`.
+    /// 
+    /// # Safety
+    /// 
+    /// Caller must ensure that the erasure really is a `T` underneath.
+    unsafe fn downcast_ref_unchecked<T>(&self) -> &T;
+}
+
+impl<'borrow, T, E: ErasureRef + ?Sized> Erasure<&'borrow T> for &'borrow E {
+    unsafe fn downcast_unchecked(self) -> &'borrow T {
+        self.downcast_ref_unchecked()
+    }
+}
@@ -0,0 +1,457 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use crate::align::{AlignedUninit, Alignment, ValidAlignment};
+use crate::erasure::{AutoTraitsOf, Erasure, ErasureMut, ErasureRef, InlineErasure, Unknown};
+use crate::util::with_data_pointer;
+#[cfg(feature = "verify-types")]
+use std::any::TypeId;
+#[cfg(feature = "verify-types")]
+use crate::erasure::type_verification::{type_info, verify_type_info};
+
+/// A trait-object (or other unsized type) stored inline, without allocation.
+///
+/// Like [InlineErasure], the value is held in an [AlignedUninit] buffer, but the metadata of the
+/// `Dyn` pointer to the value (e.g. its vtable) is held alongside it, with the `drop`
+/// implementation of the value. This allows the value to be accessed as a `Dyn`, and to be
+/// dropped, without knowing its type.
+///
+/// As stable Rust has no generic unsizing coercion, the coercion from the value to `Dyn` is
+/// supplied as a closure on creation, e.g.:
+/// ```
+/// use std::fmt::Display;
+/// use dynrsaur::erasure::InlineDyn;
+///
+/// let inline: InlineDyn<dyn Display, 16, 8> = InlineDyn::new(42u64, |x| x as &dyn Display);
+///
+/// assert_eq!(inline.to_string(), "42");
+/// ```
+///
+/// The `SIZE`/`ALIGN` const generics are the maximum size/alignment of the underlying type, as for
+/// [InlineErasure].
+///
+/// As for [ThinBox](crate::erasure::ThinBox), the auto traits of `Dyn` are ignored (as the
+/// coercion could return a field of the value), and the `InlineDyn` is only `Send`/`Sync` if
+/// `Auto` is, which requires the value to be too:
+/// ```compile_fail
+/// use std::fmt::Debug;
+/// use std::marker::PhantomData;
+/// use dynrsaur::erasure::{InlineDyn, Unknown};
+///
+/// #[derive(Debug)]
+/// struct NotSend {
+///     id: u64,
+///     _not_send: PhantomData<*const ()>
+/// }
+///
+/// // The field is `Send`, but the value isn't
+/// let inline: InlineDyn<dyn Debug + Send, 8, 8, dyn Unknown + Send> = InlineDyn::new(
+///     NotSend { id: 0, _not_send: PhantomData },
+///     |x| &x.id as &(dyn Debug + Send)
+/// );
+/// ```
+///
+/// Generic Parameters:
+/// - `Dyn`: the (usually unsized) type the value is accessed as.
+/// - `Auto`: the [auto traits](AutoTraitsOf) of the value, as `dyn Unknown` with any of `Send`
+///   and `Sync`.
+pub struct InlineDyn<Dyn: ?Sized, const SIZE: usize, const ALIGN: usize, Auto: ?Sized = dyn Unknown>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Pointer to `Dyn` which holds the metadata for the value. The data pointer is meaningless,
+    /// as the value moves with the `InlineDyn`.
+    metadata: NonNull<Dyn>,
+    /// The value itself.
+    bytes: AlignedUninit<SIZE, ALIGN>,
+    /// Pointer to [drop_impl] for the type of the value.
+    drop: unsafe fn(*mut u8),
+    /// The [type information](type_info) of the value, to verify downcasts.
+    #[cfg(feature = "verify-types")]
+    type_info: (TypeId, &'static str),
+    /// Marker indicating that we own a `Dyn`, with the auto traits of `Auto`.
+    _marker: PhantomData<(PhantomData<Dyn>, PhantomData<Auto>)>
+}
+
+// SAFETY: `InlineDyn<Dyn, .., Auto>` owns its value, which has the auto traits of `Auto` (which
+//         are checked on creation).
+unsafe impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized + Send
+> Send for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment {}
+unsafe impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized + Sync
+> Sync for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment {}
+
+impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    /// Stores `value` inline, accessing it as a `Dyn` via `coerce`.
+    ///
+    /// Panics if `T` is too big/aligned for the buffer, or if `coerce` doesn't return the
+    /// reference it was given (e.g. if it returns another value, or a non-zero-sized field of the
+    /// value).
+    pub fn new<T>(value: T, coerce: impl for<'a> FnOnce(&'a T) -> &'a Dyn) -> Self
+        where Auto: AutoTraitsOf<T>
+    {
+        // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
+        InlineErasure::<SIZE, ALIGN>::check_size_and_align_of::<T>();
+
+        let mut bytes = AlignedUninit::uninit();
+
+        let value_ptr = bytes.as_mut_ptr() as *mut T;
+
+        // SAFETY: `bytes` is big enough, and aligned enough, for a `T`.
+        unsafe { std::ptr::write(value_ptr, value) }
+
+        // SAFETY: A `T` was written to `value_ptr` above.
+        let object = coerce(unsafe { &*value_ptr }) as *const Dyn as *mut Dyn;
+
+        // A field at the start of the value has the same address, but (unless it is the whole
+        // value) a smaller size
+        // SAFETY: `object` was returned by `coerce`, so is valid while the value is.
+        let whole_value = object as *mut u8 == value_ptr as *mut u8
+            && unsafe { size_of_val(&*object) } == size_of::<T>();
+
+        if !whole_value {
+            // Don't leak the value
+            // SAFETY: A `T` was written to `value_ptr` above.
+            unsafe { std::ptr::drop_in_place(value_ptr) };
+            panic!("coercion must return the reference it was given")
+        }
+
+        Self {
+            // SAFETY: `object` has the same address as `value_ptr`, which is non-null.
+            metadata: unsafe { NonNull::new_unchecked(object) },
+            bytes,
+            drop: drop_impl::<T>,
+            #[cfg(feature = "verify-types")]
+            type_info: type_info::<T>(),
+            _marker: PhantomData
+        }
+    }
+
+    /// Panics if the value is not a `T`.
+    #[cfg(feature = "verify-types")]
+    fn verify_type<T>(&self) {
+        verify_type_info::<T>(self.type_info)
+    }
+
+    /// Returns a pointer to the value with its metadata.
+    fn object(&self) -> *const Dyn {
+        with_data_pointer(
+            self.metadata.as_ptr(),
+            self.bytes.as_ptr() as *mut u8
+        )
+    }
+
+    /// Returns a mutable pointer to the value with its metadata.
+    fn object_mut(&mut self) -> *mut Dyn {
+        with_data_pointer(
+            self.metadata.as_ptr(),
+            self.bytes.as_mut_ptr()
+        )
+    }
+}
+
+impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> Deref for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    type Target = Dyn;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The value is held in `bytes`, and the metadata is its own.
+        unsafe { &*self.object() }
+    }
+}
+
+impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> DerefMut for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The value is held in `bytes`, and the metadata is its own.
+        unsafe { &mut *self.object_mut() }
+    }
+}
+
+impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> Drop for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn drop(&mut self) {
+        // SAFETY: `drop` was created for the type of the value held in `bytes`, and is only
+        //         called once.
+        unsafe { (self.drop)(self.bytes.as_mut_ptr()) }
+    }
+}
+
+impl<
+    Dyn: ?Sized + Debug,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> Debug for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+impl<
+    Dyn: ?Sized + Display,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> Display for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.deref(), f)
+    }
+}
+
+impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized,
+    T
+> Erasure<T> for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    unsafe fn downcast_unchecked(self) -> T {
+        // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
+        InlineErasure::<SIZE, ALIGN>::check_size_and_align_of::<T>();
+
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+
+        // Don't drop the value, as we're moving it out
+        let this = ManuallyDrop::new(self);
+
+        let mut uninitialized = MaybeUninit::<T>::uninit();
+
+        std::ptr::copy_nonoverlapping(
+            this.bytes.as_ptr() as *const T,
+            uninitialized.as_mut_ptr(),
+            1
+        );
+
+        uninitialized.assume_init()
+    }
+}
+
+impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> ErasureRef for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    unsafe fn downcast_ref_unchecked<T>(&self) -> &T {
+        // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
+        InlineErasure::<SIZE, ALIGN>::check_size_and_align_of::<T>();
+
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+
+        &*(self.bytes.as_ptr() as *const T)
+    }
+}
+
+impl<
+    Dyn: ?Sized,
+    const SIZE: usize,
+    const ALIGN: usize,
+    Auto: ?Sized
+> ErasureMut for InlineDyn<Dyn, SIZE, ALIGN, Auto>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    unsafe fn downcast_mut_unchecked<T>(&mut self) -> &mut T {
+        // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
+        InlineErasure::<SIZE, ALIGN>::check_size_and_align_of::<T>();
+
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+
+        &mut *(self.bytes.as_mut_ptr() as *mut T)
+    }
+}
+
+/// Implementation of drop which remembers the type of the value.
+///
+/// # Safety
+///
+/// Must only be called when dropping the [InlineDyn], and `value` must point to a `T`.
+unsafe fn drop_impl<T>(value: *mut u8) {
+    std::ptr::drop_in_place(value as *mut T)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::fmt::{Debug, Display};
+    use crate::erasure::{Erasure, InlineDyn};
+
+    trait Strategy {
+        fn apply(&mut self, value: u32) -> u32;
+    }
+
+    struct Add(u32);
+
+    impl Strategy for Add {
+        fn apply(&mut self, value: u32) -> u32 {
+            value + self.0
+        }
+    }
+
+    struct Accumulate(u64);
+
+    impl Strategy for Accumulate {
+        fn apply(&mut self, value: u32) -> u32 {
+            self.0 += value as u64;
+            self.0 as u32
+        }
+    }
+
+    type InlineStrategy = InlineDyn<dyn Strategy, 8, 8>;
+
+    #[test]
+    fn polymorphic() {
+        let mut strategies: Vec<InlineStrategy> = vec![
+            InlineDyn::new(Add(3), |x| x as &dyn Strategy),
+            InlineDyn::new(Accumulate(10), |x| x as &dyn Strategy),
+        ];
+
+        assert_eq!(strategies[0].apply(1), 4);
+        assert_eq!(strategies[1].apply(1), 11);
+        assert_eq!(strategies[1].apply(1), 12);
+    }
+
+    #[test]
+    fn survives_moves() {
+        let inline: InlineDyn<dyn Display, 24, 8> = InlineDyn::new(
+            String::from("moved"),
+            |x| x as &dyn Display
+        );
+
+        let moved = Box::new(inline);
+
+        assert_eq!(moved.to_string(), "moved");
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_big_panics() {
+        let _inline: InlineDyn<dyn Display, 4, 8> = InlineDyn::new(0u64, |x| x as &dyn Display);
+    }
+
+    #[test]
+    fn readback() {
+        let inline: InlineDyn<dyn Display, 24, 8> = InlineDyn::new(
+            String::from("test"),
+            |x| x as &dyn Display
+        );
+
+        let unerased: String = unsafe { inline.downcast_unchecked() };
+
+        assert_eq!(unerased, "test");
+    }
+
+    #[test]
+    #[should_panic]
+    fn coercion_to_field_panics() {
+        use std::rc::Rc;
+
+        // The field is at the start of the value, but is smaller than it
+        #[repr(C)]
+        struct Pair(u64, Rc<()>);
+
+        let _inline: InlineDyn<dyn Display, 16, 8> = InlineDyn::new(
+            Pair(0, Rc::new(())),
+            |x| &x.0 as &dyn Display
+        );
+    }
+
+    #[test]
+    fn erased_drop() {
+        let dropped = Cell::new(false);
+
+        struct SetTrueOnDrop<'a>(&'a Cell<bool>);
+
+        impl Drop for SetTrueOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        trait Nothing {}
+
+        impl Nothing for SetTrueOnDrop<'_> {}
+
+        {
+            let _inline: InlineDyn<dyn Nothing, 8, 8> = InlineDyn::new(
+                SetTrueOnDrop(&dropped),
+                |x| x as &dyn Nothing
+            );
+
+            assert!(!dropped.get());
+        }
+
+        assert!(dropped.get());
+    }
+
+    #[test]
+    fn drops_whole_value() {
+        let dropped = Cell::new(false);
+
+        #[derive(Debug)]
+        struct SetTrueOnDrop<'a>(&'a Cell<bool>);
+
+        impl Drop for SetTrueOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        let inline: InlineDyn<dyn Debug, 8, 8> = InlineDyn::new(
+            SetTrueOnDrop(&dropped),
+            |x| x as &dyn Debug
+        );
+
+        assert_eq!(format!("{:?}", inline), "SetTrueOnDrop(Cell { value: false })");
+
+        drop(inline);
+
+        assert!(dropped.get());
+    }
+}
@@ -1,7 +1,15 @@
-use std::mem::MaybeUninit;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ptr::{copy_nonoverlapping, drop_in_place, NonNull};
 use const_panic::concat_panic;
-use crate::align::{AlignedBytes, Alignment, ValidAlignment};
-use crate::erasure::{Erase, Erasure};
+use sealed::sealed;
+use crate::align::{AlignedUninit, Alignment, ValidAlignment};
+use crate::erasure::{AllocError, Erase, Erasure, ErasureMut, ErasureRef, TryErase, Unknown};
+use crate::erasure::TranscodeErrorKind;
+use crate::erasure::unknown::UnknownMetadata;
+#[cfg(feature = "verify-types")]
+use crate::erasure::type_verification::verify_type_info;
+#[cfg(feature = "telemetry")]
+use crate::erasure::telemetry::{record, TelemetryEventKind};
 
 /// An erasure of some underlying type that exists inline (i.e. the data for the erased-type value
 /// is within this struct, as opposed to an indirection to the data).
@@ -10,12 +18,22 @@ use crate::erasure::{Erase, Erasure};
 /// Currently stable Rust can't compare these to [size_of]/[align_of] in const generics, so
 /// enforcing this is a run-time check.
 /// 
+/// The metadata of the value (its layout, drop glue and, with the `verify-types` feature, its
+/// type) is recorded alongside the buffer, so the value is dropped with the erasure, and can be
+/// [transcoded](crate::erasure::Transcode) into other erasures. With the `telemetry` feature,
+/// values aren't counted when erased via [InlineErasure::new] (as it is `const`), only via the other
+/// constructors (e.g. [Erase::erase]).
+/// 
 /// TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
-#[repr(transparent)]
 pub struct InlineErasure<const SIZE: usize, const ALIGN: usize>
     where Alignment<ALIGN>: ValidAlignment
 {
-    bytes: AlignedBytes<SIZE, ALIGN>
+    bytes: AlignedUninit<SIZE, ALIGN>,
+    /// The metadata of the value.
+    metadata: UnknownMetadata,
+    /// Whether the value was counted by telemetry when erased.
+    #[cfg(feature = "telemetry")]
+    recorded: bool
 }
 
 impl<
@@ -25,18 +43,98 @@ impl<
     where Alignment<ALIGN>: ValidAlignment
 {
     /// Erases the given `value` inline.
+    /// 
+    /// With the `telemetry` feature, the value is never counted (nor are its downcast or drop), as
+    /// this is `const`. Use [Erase::erase] or [InlineErasure::new_with] to count it.
     pub const fn new<T>(value: T) -> Self {
         // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
         Self::check_size_and_align_of::<T>();
         
-        let mut bytes = AlignedBytes::zeroed();
+        let mut bytes = AlignedUninit::uninit();
 
-        unsafe { std::ptr::write(bytes.as_bytes_mut() as *mut u8 as *mut T, value) }
+        // SAFETY: `bytes` is big enough, and aligned enough, for a `T`.
+        unsafe { std::ptr::write(bytes.as_mut_ptr() as *mut T, value) }
+        
+        Self::from_bytes::<T>(bytes)
+    }
+    
+    /// Erases the value returned by `f` inline.
+    /// 
+    /// The value may still be passed through the stack (the compiler may or may not write it
+    /// directly into the buffer). To avoid constructing a whole `T` first, initialise it in-place
+    /// via [InlineErasure::new_in_place].
+    pub fn new_with<T>(f: impl FnOnce() -> T) -> Self {
+        // SAFETY: `write` initialises the value.
+        unsafe { Self::new_in_place(|slot| { slot.write(f()); }) }
+    }
+    
+    /// Erases a `T` inline, which is initialised in-place by `init` (e.g. field by field, via
+    /// [MaybeUninit::as_mut_ptr]).
+    /// 
+    /// The value is initialised in the buffer, but the erasure (like any value) is then returned
+    /// by value, so the buffer may be moved (i.e. copied) afterwards.
+    /// 
+    /// # Safety
+    /// 
+    /// `init` must fully initialise the value.
+    pub unsafe fn new_in_place<T>(init: impl FnOnce(&mut MaybeUninit<T>)) -> Self {
+        // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
+        Self::check_size_and_align_of::<T>();
+        
+        let mut bytes = AlignedUninit::uninit();
+        
+        // Initialised before creating the erasure, so a panic doesn't drop an uninitialised value
+        init(&mut *(bytes.as_mut_ptr() as *mut MaybeUninit<T>));
+        
+        #[allow(unused_mut)]
+        let mut erasure = Self::from_bytes::<T>(bytes);
+        
+        #[cfg(feature = "telemetry")]
+        {
+            erasure.recorded = true;
+            erasure.record(TelemetryEventKind::Erased);
+        }
         
-        Self { bytes }
+        erasure
     }
     
-    const fn check_size_and_align_of<T>() {
+    /// Creates the erasure of the `T` in `bytes`.
+    const fn from_bytes<T>(bytes: AlignedUninit<SIZE, ALIGN>) -> Self {
+        Self {
+            bytes,
+            metadata: UnknownMetadata::of::<T>(),
+            #[cfg(feature = "telemetry")]
+            recorded: false
+        }
+    }
+    
+    /// Returns a pointer to the value, with its metadata.
+    fn object(&self) -> NonNull<dyn Unknown> {
+        // SAFETY: `bytes` is not null.
+        self.metadata.with_data(unsafe { NonNull::new_unchecked(self.bytes.as_ptr() as *mut u8) })
+    }
+    
+    /// Records a [telemetry event](crate::erasure::telemetry) for the value, if it was counted
+    /// when erased.
+    #[cfg(feature = "telemetry")]
+    fn record(&self, kind: TelemetryEventKind) {
+        if self.recorded {
+            // SAFETY: The value is valid for the lifetime of the erasure.
+            let object = unsafe { self.object().as_ref() };
+            
+            record::<Self>(kind, "InlineErasure", object.__type_info().1, size_of_val(object))
+        }
+    }
+    
+    /// Panics if the value is not a `T`.
+    #[cfg(feature = "verify-types")]
+    fn verify_type<T>(&self) {
+        // SAFETY: The value is valid for the lifetime of the erasure.
+        verify_type_info::<T>(unsafe { self.object().as_ref() }.__type_info())
+    }
+    
+    /// Panics if a `T` is too big, or too aligned, to be stored in the buffer.
+    pub(crate) const fn check_size_and_align_of<T>() {
         if size_of::<T>() > SIZE {
             // TODO: Add type_name::<T> once const-stable
             concat_panic!(
@@ -72,10 +170,19 @@ impl<
         // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
         Self::check_size_and_align_of::<T>();
         
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+        
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Downcast);
+        
+        // Moving the value out, so the erasure is not dropped
+        let this = ManuallyDrop::new(self);
+        
         let mut uninitialized = MaybeUninit::<T>::uninit();
         
-        std::ptr::copy_nonoverlapping(
-            self.bytes.as_bytes() as *const u8 as *const T,
+        copy_nonoverlapping(
+            this.bytes.as_ptr() as *const T,
             uninitialized.as_mut_ptr(),
             1
         );
@@ -93,38 +200,178 @@ impl<
     where Alignment<ALIGN>: ValidAlignment
 {
     fn erase(value: T) -> Self {
-        Self::new(value)
+        #[allow(unused_mut)]
+        let mut erasure = Self::new(value);
+        
+        #[cfg(feature = "telemetry")]
+        {
+            erasure.recorded = true;
+            erasure.record(TelemetryEventKind::Erased);
+        }
+        
+        erasure
     }
 }
 
 impl<
-    'borrow,
     const SIZE: usize,
     const ALIGN: usize,
-    T: 'borrow
-> Erasure<&'borrow mut T> for &'borrow mut InlineErasure<SIZE, ALIGN>
-    where Alignment<ALIGN>: ValidAlignment 
+    T
+> TryErase<T> for InlineErasure<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
 {
-    unsafe fn downcast_unchecked(self) -> &'borrow mut T {
+    /// Never fails, as `InlineErasure` doesn't allocate.
+    fn try_erase(value: T) -> Result<Self, AllocError> {
+        Ok(Self::erase(value))
+    }
+}
+
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> Drop for InlineErasure<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn drop(&mut self) {
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Dropped);
+        
+        // SAFETY: The value is valid, and only dropped once.
+        unsafe { drop_in_place(self.object().as_ptr()) }
+    }
+}
+
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> ErasureRef for InlineErasure<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    unsafe fn downcast_ref_unchecked<T>(&self) -> &T {
         // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
         InlineErasure::<SIZE, ALIGN>::check_size_and_align_of::<T>();
         
-        &mut *(self as *mut InlineErasure<SIZE, ALIGN> as *mut T)
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+        
+        &*(self.bytes.as_ptr() as *const T)
     }
 }
 
 impl<
-    'borrow,
     const SIZE: usize,
-    const ALIGN: usize,
-    T: 'borrow
-> Erasure<&'borrow T> for &'borrow InlineErasure<SIZE, ALIGN>
-    where Alignment<ALIGN>: ValidAlignment
+    const ALIGN: usize
+> ErasureMut for InlineErasure<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment 
 {
-    unsafe fn downcast_unchecked(self) -> &'borrow T {
+    unsafe fn downcast_mut_unchecked<T>(&mut self) -> &mut T {
         // TODO: Use const generics to restrict `T` to validly-sized/aligned types once stable.
         InlineErasure::<SIZE, ALIGN>::check_size_and_align_of::<T>();
         
-        &*(self as *const InlineErasure<SIZE, ALIGN> as *const T)
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+        
+        &mut *(self.bytes.as_mut_ptr() as *mut T)
+    }
+}
+
+#[sealed]
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> crate::erasure::transcode::TranscodeSource for InlineErasure<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    fn object(&self) -> NonNull<dyn Unknown> {
+        InlineErasure::object(self)
     }
-}
\ No newline at end of file
+
+    unsafe fn forget_value(self) {
+        #[cfg(feature = "telemetry")]
+        self.record(TelemetryEventKind::Downcast);
+        
+        // The buffer doesn't own any memory, so nothing to release
+        let _ = ManuallyDrop::new(self);
+    }
+}
+
+#[sealed]
+impl<
+    const SIZE: usize,
+    const ALIGN: usize
+> crate::erasure::transcode::TranscodeTarget for InlineErasure<SIZE, ALIGN>
+    where Alignment<ALIGN>: ValidAlignment
+{
+    unsafe fn read_from(object: NonNull<dyn Unknown>) -> Result<Self, TranscodeErrorKind> {
+        let (size, align) = (size_of_val(object.as_ref()), align_of_val(object.as_ref()));
+        
+        if size > SIZE {
+            return Err(TranscodeErrorKind::TooBig { max: SIZE });
+        }
+        
+        if align > ALIGN {
+            return Err(TranscodeErrorKind::TooAligned { max: ALIGN });
+        }
+        
+        let mut bytes = AlignedUninit::<SIZE, ALIGN>::uninit();
+        
+        copy_nonoverlapping(object.as_ptr() as *const u8, bytes.as_mut_ptr(), size);
+        
+        #[allow(unused_mut)]
+        let mut erasure = Self {
+            bytes,
+            metadata: UnknownMetadata::of_object(object),
+            #[cfg(feature = "telemetry")]
+            recorded: false
+        };
+        
+        #[cfg(feature = "telemetry")]
+        {
+            erasure.recorded = true;
+            erasure.record(TelemetryEventKind::Erased);
+        }
+        
+        Ok(erasure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::MaybeUninit;
+    use std::ptr::addr_of_mut;
+    use crate::erasure::{Erasure, InlineErasure};
+
+    #[test]
+    fn new_with() {
+        let erased: InlineErasure<4096, 8> = InlineErasure::new_with(|| [42u64; 512]);
+
+        let unerased: &[u64; 512] = unsafe { (&erased).downcast_unchecked() };
+
+        assert!(unerased.iter().all(|value| *value == 42));
+    }
+
+    #[test]
+    fn new_in_place() {
+        struct Pair {
+            name: String,
+            values: [u32; 256]
+        }
+
+        let erased: InlineErasure<1056, 8> = unsafe {
+            InlineErasure::new_in_place(|slot: &mut MaybeUninit<Pair>| {
+                let pair = slot.as_mut_ptr();
+
+                addr_of_mut!((*pair).name).write(String::from("test"));
+
+                for (index, value) in (*addr_of_mut!((*pair).values)).iter_mut().enumerate() {
+                    *value = index as u32;
+                }
+            })
+        };
+
+        let unerased: Pair = unsafe { erased.downcast_unchecked() };
+
+        assert_eq!(unerased.name, "test");
+        assert_eq!(unerased.values[255], 255);
+    }
+}
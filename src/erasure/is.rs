@@ -1,6 +1,6 @@
 use std::any::type_name;
 use std::marker::PhantomData;
-use crate::erasure::{defer_downcast_unchecked_to_try_downcast, Erasure, TaggedErasure};
+use crate::erasure::{defer_downcast_unchecked_to_try_downcast, DowncastError, Erasure, TaggedErasure};
 
 /// Context-type asserting that the given erasure *is* a `T`.
 /// 
@@ -28,6 +28,19 @@ impl<T, E: TaggedErasure<T>> Is<T, E> {
         }
     }
     
+    /// Checks if the given [erasure][TaggedErasure] is an erased-`T`.
+    /// 
+    /// Returns:
+    /// - `Ok(Self)`: if the erasure is an erased-`T`.
+    /// - `Err(error)`: if the erasure is not an erased-`T`, where the [error](DowncastError)
+    ///   holds the erasure.
+    pub fn new_or_error(erasure: E) -> Result<Self, DowncastError<E>> {
+        Self::new(erasure).map_err(|erasure| {
+            let actual = erasure.underlying_type_name();
+            DowncastError::new(erasure, type_name::<T>(), actual)
+        })
+    }
+    
     /// Reclaims the erasure from the context.
     pub fn into_inner(self) -> E {
         self.erasure
@@ -37,11 +50,15 @@ impl<T, E: TaggedErasure<T>> Is<T, E> {
     pub fn downcast(self) -> T {
         match self.erasure.try_downcast() {
             Ok(downcast) => downcast,
-            Err(_) => panic!(
-                "inconsistent TaggedErasure impl: {} claims to be a {} but isn't",
-                type_name::<E>(),
-                type_name::<T>()
-            )
+            Err(erasure) => {
+                let actual = erasure.underlying_type_name();
+                panic!(
+                    "inconsistent TaggedErasure impl: {} claims to be a {} but isn't ({})",
+                    type_name::<E>(),
+                    type_name::<T>(),
+                    DowncastError::new(erasure, type_name::<T>(), actual)
+                )
+            }
         }
     }
 }
@@ -60,13 +77,20 @@ impl<T, E: TaggedErasure<T>> TaggedErasure<T> for Is<T, E> {
     fn try_downcast(self) -> Result<T, Self> {
         Ok(self.downcast())
     }
+    
+    fn underlying_type_name(&self) -> Option<&'static str> {
+        Some(type_name::<T>())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::any::Any;
     use std::ops::Deref;
-    use crate::erasure::Is;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use crate::erasure::{defer_downcast_unchecked_to_try_downcast, Erasure, ErasedBox, Is, TaggedErasure};
 
     #[test]
     fn is() {
@@ -79,4 +103,84 @@ mod tests {
         
         assert_eq!(is.downcast(), STRING);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn is_rc() {
+        let erased: Rc<dyn Any> = Rc::new(5u32);
+        let other = erased.clone();
+        
+        let Err(erased) = Is::<Rc<i32>, _>::new(erased) else {
+            panic!("erased is an Rc<u32>")
+        };
+        
+        let is = Is::<Rc<u32>, _>::new(erased)
+            .expect("erased is an Rc<u32>");
+        
+        let rc = is.downcast();
+        
+        assert_eq!(*rc, 5);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(other);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+    
+    #[test]
+    fn is_arc() {
+        let erased: Arc<dyn Any + Send + Sync> = Arc::new(String::from("shared"));
+        
+        let is = Is::<Arc<String>, _>::new(erased)
+            .expect("erased is an Arc<String>");
+        
+        assert_eq!(is.downcast().as_str(), "shared");
+    }
+    
+    #[test]
+    fn is_pinned_box() {
+        let erased: Pin<Box<dyn Any>> = Box::pin(5u8);
+        
+        let is = Is::<Pin<Box<u8>>, _>::new(erased)
+            .expect("erased is a Pin<Box<u8>>");
+        
+        assert_eq!(*is.downcast(), 5);
+    }
+    
+    #[test]
+    fn erased_box_of_rc() {
+        let erased = ErasedBox::<Rc<dyn Any>>::new(Rc::new(String::from("boxed")));
+        
+        let unerased: Rc<String> = unsafe { erased.downcast_unchecked() };
+        
+        assert_eq!(unerased.as_str(), "boxed");
+    }
+    
+    #[test]
+    #[should_panic(expected = "(underlying type is 'u8')")]
+    fn inconsistent_downcast_panics_with_actual_type() {
+        /// Claims to be a `u32`, but is always a `u8`.
+        struct Liar;
+        
+        impl Erasure<u32> for Liar {
+            unsafe fn downcast_unchecked(self) -> u32 {
+                defer_downcast_unchecked_to_try_downcast(self)
+            }
+        }
+        
+        impl TaggedErasure<u32> for Liar {
+            fn is(&self) -> bool {
+                true
+            }
+            
+            fn try_downcast(self) -> Result<u32, Self> {
+                Err(self)
+            }
+            
+            fn underlying_type_name(&self) -> Option<&'static str> {
+                Some("u8")
+            }
+        }
+        
+        let is = Is::<u32, _>::new(Liar).ok().expect("Liar claims to be a u32");
+        
+        is.downcast();
+    }
+}
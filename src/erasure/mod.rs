@@ -5,15 +5,51 @@
 //! These 2 types fulfil the same purpose, that is, being [untagged erasures](Erasure) of any 
 //! ([sized](Sized)) type, held on the heap. The difference is in their layout. `AutoDropUnique` is
 //! 1 word in size vs. `Box<dyn Unknown>`'s 2 words. However, `AutoDropUnique` keeps the
-//! [drop](Drop::drop) implementation with the value on the heap, so the heap allocation is bigger
-//! (how much bigger depends on the size/alignment of the underlying value).
+//! `Box<dyn Unknown>`-style pointer (whose metadata provides the layout and [drop](Drop::drop)
+//! implementation) with the value on the heap, so the heap allocation is bigger (how much bigger
+//! depends on the size/alignment of the underlying value).
+//! 
+//! ## Transcoding
+//! 
+//! As [`AutoDropUnique`], [`InlineErasure`] and [`Box<dyn Unknown>`](Unknown) all record the
+//! layout and drop glue of their value, an erased value can be moved between them without knowing
+//! its type (see [`Transcode`]). This fails if the target is too small (or not aligned enough) for
+//! the value.
+//! 
+//! ## Type verification
+//! 
+//! Downcasting an untagged erasure to the wrong type is undefined behaviour. To help track down
+//! such bugs, the `verify-types` feature adds the type of the value to the metadata recorded by
+//! [`AutoDropUnique`], [`InlineErasure`], [`Box<dyn Unknown>`](Unknown) (and references to
+//! `dyn Unknown`), [`ThinBox`], [`InlineDyn`] and [`ErasedIter`], and their downcasts panic
+//! (naming both types) on a mismatch. This adds a check to every downcast, so it is off by
+//! default.
+//! 
+//! Downcasts of [`ErasedRef`]/[`ErasedMut`] and of raw pointers to `dyn Unknown` (e.g.
+//! `NonNull<dyn Unknown>`) are not verified: the former are a single word, with no room for the
+//! type, and the latter may dangle, so their vtable can't be queried for it.
+//! 
+//! The `telemetry` feature (which implies `verify-types`) additionally counts the values held by
+//! some of these erasures, per type, and reports their lifecycle events (see the `telemetry`
+//! module).
+
+mod alloc_error;
+pub use alloc_error::AllocError;
 
 mod auto_drop_unique;
 pub use auto_drop_unique::AutoDropUnique;
 
+mod auto_traits_of;
+pub use auto_traits_of::AutoTraitsOf;
+
 mod defer_downcast_unchecked_to_try_downcast;
 pub use defer_downcast_unchecked_to_try_downcast::defer_downcast_unchecked_to_try_downcast;
 
+mod downcast_error;
+pub use downcast_error::DowncastError;
+
+mod downcast_match;
+
 mod erase;
 pub use erase::Erase;
 
@@ -23,17 +59,55 @@ pub use erased::Erased;
 mod erased_box;
 pub use erased_box::ErasedBox;
 
+mod erased_error;
+pub use erased_error::ErasedError;
+
+mod erased_iter;
+pub use erased_iter::ErasedIter;
+
+mod erased_ref;
+pub use erased_ref::{ErasedMut, ErasedRef};
+
 mod erasure;
 pub use erasure::Erasure;
 
+mod erasure_mut;
+pub use erasure_mut::ErasureMut;
+
+mod erasure_ref;
+pub use erasure_ref::ErasureRef;
+
+mod inline_dyn;
+pub use inline_dyn::InlineDyn;
+
 mod inline_erasure;
 pub use inline_erasure::InlineErasure;
 
 mod is;
 pub use is::Is;
 
+mod tagged_erased_ref;
+pub use tagged_erased_ref::{TaggedErasedMut, TaggedErasedRef};
+
 mod tagged_erasure;
 pub use tagged_erasure::TaggedErasure;
 
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+
+mod thin_box;
+pub use thin_box::ThinBox;
+
+mod transcode;
+pub use transcode::{Transcode, TranscodeError, TranscodeErrorKind};
+
+mod try_erase;
+pub use try_erase::TryErase;
+
+mod type_tag;
+
+#[cfg(feature = "verify-types")]
+mod type_verification;
+
 mod unknown;
 pub use unknown::Unknown;
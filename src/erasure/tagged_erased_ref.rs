@@ -0,0 +1,184 @@
+use crate::erasure::{defer_downcast_unchecked_to_try_downcast, Erase, ErasedMut, ErasedRef, Erasure, TaggedErasure};
+use crate::erasure::type_tag::TypeTag;
+
+/// A [tagged](TaggedErasure) version of [ErasedRef], which can check the type of the referenced
+/// value before downcasting.
+///
+/// Two words in size (the same as `&dyn Any`), but the type check is a single comparison after
+/// an indirect call, rather than a virtual call.
+///
+/// Generic Parameters:
+/// - `'lifetime`: the lifetime of the original reference.
+#[derive(Copy, Clone)]
+pub struct TaggedErasedRef<'lifetime> {
+    /// The untagged reference.
+    erased: ErasedRef<'lifetime>,
+    /// The tag of the referenced type.
+    tag: TypeTag
+}
+
+impl<'lifetime> TaggedErasedRef<'lifetime> {
+    /// Erases the type of the given reference.
+    pub fn new<T: 'static>(reference: &'lifetime T) -> Self {
+        TaggedErasedRef {
+            erased: ErasedRef::new(reference),
+            tag: TypeTag::of::<T>()
+        }
+    }
+
+    /// Discards the tag.
+    pub fn untagged(self) -> ErasedRef<'lifetime> {
+        self.erased
+    }
+}
+
+impl<'lifetime, T: 'static> Erasure<&'lifetime T> for TaggedErasedRef<'lifetime> {
+    unsafe fn downcast_unchecked(self) -> &'lifetime T {
+        defer_downcast_unchecked_to_try_downcast(self)
+    }
+}
+
+impl<'lifetime, T: 'static> TaggedErasure<&'lifetime T> for TaggedErasedRef<'lifetime> {
+    fn is(&self) -> bool {
+        self.tag.is::<T>()
+    }
+
+    fn underlying_type_name(&self) -> Option<&'static str> {
+        Some(self.tag.type_name())
+    }
+
+    fn try_downcast(self) -> Result<&'lifetime T, Self> {
+        if self.tag.is::<T>() {
+            // SAFETY: Checked the tag above.
+            Ok(unsafe { self.erased.downcast_unchecked() })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<'lifetime, T: 'static> Erase<&'lifetime T> for TaggedErasedRef<'lifetime> {
+    fn erase(value: &'lifetime T) -> Self {
+        TaggedErasedRef::new(value)
+    }
+}
+
+impl<'lifetime> From<TaggedErasedMut<'lifetime>> for TaggedErasedRef<'lifetime> {
+    fn from(value: TaggedErasedMut<'lifetime>) -> Self {
+        TaggedErasedRef {
+            erased: value.erased.into(),
+            tag: value.tag
+        }
+    }
+}
+
+/// A [tagged](TaggedErasure) version of [ErasedMut], which can check the type of the referenced
+/// value before downcasting.
+///
+/// Two words in size (the same as `&mut dyn Any`), but the type check is a single comparison after
+/// an indirect call, rather than a virtual call.
+///
+/// Generic Parameters:
+/// - `'lifetime`: the lifetime of the original reference.
+pub struct TaggedErasedMut<'lifetime> {
+    /// The untagged reference.
+    erased: ErasedMut<'lifetime>,
+    /// The tag of the referenced type.
+    tag: TypeTag
+}
+
+impl<'lifetime> TaggedErasedMut<'lifetime> {
+    /// Erases the type of the given mutable reference.
+    pub fn new<T: 'static>(reference: &'lifetime mut T) -> Self {
+        TaggedErasedMut {
+            erased: ErasedMut::new(reference),
+            tag: TypeTag::of::<T>()
+        }
+    }
+
+    /// Reborrows the erased reference for a shorter lifetime.
+    pub fn reborrow(&mut self) -> TaggedErasedMut<'_> {
+        TaggedErasedMut {
+            erased: self.erased.reborrow(),
+            tag: self.tag
+        }
+    }
+
+    /// Discards the tag.
+    pub fn untagged(self) -> ErasedMut<'lifetime> {
+        self.erased
+    }
+}
+
+impl<'lifetime, T: 'static> Erasure<&'lifetime mut T> for TaggedErasedMut<'lifetime> {
+    unsafe fn downcast_unchecked(self) -> &'lifetime mut T {
+        defer_downcast_unchecked_to_try_downcast(self)
+    }
+}
+
+impl<'lifetime, T: 'static> TaggedErasure<&'lifetime mut T> for TaggedErasedMut<'lifetime> {
+    fn is(&self) -> bool {
+        self.tag.is::<T>()
+    }
+
+    fn underlying_type_name(&self) -> Option<&'static str> {
+        Some(self.tag.type_name())
+    }
+
+    fn try_downcast(self) -> Result<&'lifetime mut T, Self> {
+        if self.tag.is::<T>() {
+            // SAFETY: Checked the tag above.
+            Ok(unsafe { self.erased.downcast_unchecked() })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<'lifetime, T: 'static> Erase<&'lifetime mut T> for TaggedErasedMut<'lifetime> {
+    fn erase(value: &'lifetime mut T) -> Self {
+        TaggedErasedMut::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::erasure::{Is, TaggedErasedMut, TaggedErasedRef, TaggedErasure};
+
+    #[test]
+    fn double_word_size() {
+        assert_eq!(size_of::<TaggedErasedRef>(), 2 * size_of::<usize>());
+        assert_eq!(size_of::<TaggedErasedMut>(), 2 * size_of::<usize>());
+    }
+
+    #[test]
+    fn is() {
+        let value = 42u32;
+
+        let erased = TaggedErasedRef::new(&value);
+
+        assert!(TaggedErasure::<&u32>::is(&erased));
+        assert!(!TaggedErasure::<&i32>::is(&erased));
+
+        let is = Is::<&u32, _>::new(erased).ok().expect("erased is a &u32");
+
+        assert_eq!(is.downcast(), &42);
+    }
+
+    #[test]
+    fn try_downcast_mut() {
+        let mut value = String::from("test");
+
+        let mut erased = TaggedErasedMut::new(&mut value);
+
+        let erased_again = match TaggedErasure::<&mut u32>::try_downcast(erased.reborrow()) {
+            Ok(_) => panic!("not a &mut u32"),
+            Err(erased) => erased
+        };
+
+        let string: &mut String = erased_again.try_downcast().ok().unwrap();
+        string.push_str("ed");
+
+        assert_eq!(value, "tested");
+    }
+}
@@ -1,8 +1,11 @@
-use std::any::Any;
+use std::any::{type_name, Any};
 use std::mem::transmute;
 use std::ops::Deref;
 use std::panic::{RefUnwindSafe, UnwindSafe};
-use crate::erasure::Erasure;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use crate::erasure::{DowncastError, Erasure};
 use crate::for_all_combinations;
 
 /// Indicates that the type is a potential erasure of `T`, and contains information
@@ -21,6 +24,22 @@ pub trait TaggedErasure<T>: Erasure<T> {
     /// Downcast this erasure into a `T`, if the underlying type is such, returning `self`
     /// unchanged if not.
     fn try_downcast(self) -> Result<T, Self>;
+    
+    /// The name of the underlying type of this erasure, if the tag records it.
+    /// 
+    /// Only used for diagnostics, e.g. in [DowncastError].
+    fn underlying_type_name(&self) -> Option<&'static str> {
+        None
+    }
+    
+    /// Downcast this erasure into a `T`, if the underlying type is such, returning a
+    /// [DowncastError] (which holds `self` unchanged) if not.
+    fn try_downcast_or_error(self) -> Result<T, DowncastError<Self>> {
+        let actual = self.underlying_type_name();
+        
+        self.try_downcast()
+            .map_err(|self_| DowncastError::new(self_, type_name::<T>(), actual))
+    }
 }
 macro_rules! impl_tagged_erasure_for_any {
     ($($traits:ident),*) => {
@@ -63,6 +82,58 @@ macro_rules! impl_tagged_erasure_for_any {
                 }
             }
         }
+        
+        impl<T: 'static$( + $traits)*> TaggedErasure<Rc<T>> for Rc<dyn Any$( + $traits)*> {
+            fn is(&self) -> bool {
+                <dyn Any>::is::<T>(self.deref())
+            }
+        
+            fn try_downcast(self) -> Result<Rc<T>, Self> {
+                if <dyn Any>::is::<T>(self.deref()) {
+                    // TODO: Change to <Rc<dyn Any>>::downcast when stable for all auto-traits.
+                    // SAFETY: Above if-statement, and the pointer is from Rc::into_raw
+                    Ok(unsafe { Rc::from_raw(Rc::into_raw(self) as *const T) })
+                } else {
+                    Err(self)
+                }
+            }
+        }
+        
+        impl<T: 'static$( + $traits)*> TaggedErasure<Arc<T>> for Arc<dyn Any$( + $traits)*> {
+            fn is(&self) -> bool {
+                <dyn Any>::is::<T>(self.deref())
+            }
+        
+            fn try_downcast(self) -> Result<Arc<T>, Self> {
+                if <dyn Any>::is::<T>(self.deref()) {
+                    // TODO: Change to <Arc<dyn Any + Send + Sync>>::downcast when stable for all
+                    //       auto-traits.
+                    // SAFETY: Above if-statement, and the pointer is from Arc::into_raw
+                    Ok(unsafe { Arc::from_raw(Arc::into_raw(self) as *const T) })
+                } else {
+                    Err(self)
+                }
+            }
+        }
+        
+        impl<T: 'static$( + $traits)*> TaggedErasure<Pin<Box<T>>> for Pin<Box<dyn Any$( + $traits)*>> {
+            fn is(&self) -> bool {
+                <dyn Any>::is::<T>(self.deref())
+            }
+        
+            fn try_downcast(self) -> Result<Pin<Box<T>>, Self> {
+                if <dyn Any>::is::<T>(self.deref()) {
+                    // SAFETY: The box is immediately re-pinned, and the above if-statement
+                    //         ensures the pointer is to a T.
+                    Ok(unsafe {
+                        let boxed = Pin::into_inner_unchecked(self);
+                        Pin::new_unchecked(Box::from_raw(Box::into_raw(boxed) as *mut T))
+                    })
+                } else {
+                    Err(self)
+                }
+            }
+        }
     };
 }
 
@@ -0,0 +1,452 @@
+//! Telemetry for erased values (enabled by the `telemetry` feature).
+//!
+//! [AutoDropUnique](crate::erasure::AutoDropUnique), [InlineErasure](crate::erasure::InlineErasure)
+//! and [ErasedBox](crate::erasure::ErasedBox) report [events](TelemetryEvent) when values are
+//! erased, downcast or dropped. These are counted per container and underlying type (see
+//! [stats]/[report]), and can be forwarded elsewhere (e.g. to a logger) by [setting a
+//! hook](set_hook).
+//!
+//! Events are only recorded by the outermost container, e.g. the value of an
+//! `ErasedBox<AutoDropUnique>` is counted for the `ErasedBox`, not the `AutoDropUnique`. Values
+//! created by `const` constructors (such as [InlineErasure::new](crate::erasure::InlineErasure::new))
+//! can't be recorded, so they aren't counted at all (nor are their later events, e.g. their drop),
+//! and don't appear in the [stats] or [report].
+
+use std::any::type_name;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::{Mutex, RwLock};
+
+/// What happened to an erased value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TelemetryEventKind {
+    /// The value was placed into an erasure.
+    Erased,
+    /// The value was moved back out of its erasure.
+    Downcast,
+    /// The value was dropped along with its erasure.
+    Dropped
+}
+
+/// An event in the lifetime of an erased value.
+#[derive(Copy, Clone, Debug)]
+pub struct TelemetryEvent {
+    /// What happened to the value.
+    kind: TelemetryEventKind,
+    /// The name of the erasure type holding the value.
+    container: &'static str,
+    /// The name of the type of the value.
+    type_name: &'static str,
+    /// The size of the value, in bytes.
+    size: usize
+}
+
+impl TelemetryEvent {
+    /// What happened to the value.
+    pub fn kind(&self) -> TelemetryEventKind {
+        self.kind
+    }
+
+    /// The name of the erasure type holding the value.
+    pub fn container(&self) -> &'static str {
+        self.container
+    }
+
+    /// The name of the type of the value.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The size of the value, in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl Display for TelemetryEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {}<{}> ({} bytes)",
+            self.kind,
+            self.container,
+            self.type_name,
+            self.size
+        )
+    }
+}
+
+/// Receiver of [telemetry events](TelemetryEvent), installed with [set_hook].
+///
+/// Implemented for closures taking a `&TelemetryEvent`.
+pub trait TelemetryHook: Send + Sync {
+    /// Called for each event, after the [stats] have been updated.
+    ///
+    /// Must not erase (or drop, downcast, etc.) instrumented values, as the hook is not reentrant.
+    fn on_event(&self, event: &TelemetryEvent);
+}
+
+impl<F: Fn(&TelemetryEvent) + Send + Sync> TelemetryHook for F {
+    fn on_event(&self, event: &TelemetryEvent) {
+        self(event)
+    }
+}
+
+/// Counters for the values of one type held in one erasure type.
+#[derive(Copy, Clone, Debug)]
+pub struct ErasureStats {
+    /// The name of the erasure type holding the values.
+    container: &'static str,
+    /// The name of the type of the values.
+    type_name: &'static str,
+    /// The number of values currently erased.
+    live: usize,
+    /// The total size of the values currently erased.
+    live_bytes: usize,
+    /// The most values erased at any one time.
+    peak: usize,
+    /// The largest total size of the values erased at any one time.
+    peak_bytes: usize,
+    /// The number of values ever erased.
+    total: usize
+}
+
+impl ErasureStats {
+    /// The name of the erasure type holding the values.
+    pub fn container(&self) -> &'static str {
+        self.container
+    }
+
+    /// The name of the type of the values.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The number of values currently erased.
+    pub fn live(&self) -> usize {
+        self.live
+    }
+
+    /// The total size (in bytes) of the values currently erased.
+    pub fn live_bytes(&self) -> usize {
+        self.live_bytes
+    }
+
+    /// The most values erased at any one time.
+    pub fn peak(&self) -> usize {
+        self.peak
+    }
+
+    /// The largest total size (in bytes) of the values erased at any one time.
+    pub fn peak_bytes(&self) -> usize {
+        self.peak_bytes
+    }
+
+    /// The number of values ever erased.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Updates the counters for `event`.
+    fn record(&mut self, event: &TelemetryEvent) {
+        match event.kind {
+            TelemetryEventKind::Erased => {
+                self.live += 1;
+                self.live_bytes += event.size;
+                self.total += 1;
+                self.peak = self.peak.max(self.live);
+                self.peak_bytes = self.peak_bytes.max(self.live_bytes);
+            },
+            TelemetryEventKind::Downcast | TelemetryEventKind::Dropped => {
+                self.live = self.live.saturating_sub(1);
+                self.live_bytes = self.live_bytes.saturating_sub(event.size);
+            }
+        }
+    }
+}
+
+/// The outstanding erasures at the time of a call to [report].
+///
+/// [Displays](Display) one line per container/type with live values.
+#[derive(Clone, Debug)]
+pub struct TelemetryReport {
+    /// The stats with live values.
+    outstanding: Vec<ErasureStats>
+}
+
+impl TelemetryReport {
+    /// The stats of each container/type with live values.
+    pub fn outstanding(&self) -> &[ErasureStats] {
+        &self.outstanding
+    }
+}
+
+impl Display for TelemetryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.outstanding.is_empty() {
+            return write!(f, "no outstanding erasures");
+        }
+
+        for (index, stats) in self.outstanding.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "{} live {}<{}> ({} bytes, peak {} values/{} bytes, {} total)",
+                stats.live,
+                stats.container,
+                stats.type_name,
+                stats.live_bytes,
+                stats.peak,
+                stats.peak_bytes,
+                stats.total
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The stats for each container/type, keyed by container and type name.
+static STATS: Mutex<BTreeMap<(&'static str, &'static str), ErasureStats>> = Mutex::new(BTreeMap::new());
+
+/// The installed hook, if any.
+static HOOK: RwLock<Option<Box<dyn TelemetryHook>>> = RwLock::new(None);
+
+thread_local! {
+    /// The type name of the inner erasure whose next event is skipped, as it is recorded by its
+    /// container instead (see [outermost]).
+    static INNER: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Installs `hook` to receive all subsequent events, returning the previous hook (if any).
+pub fn set_hook(hook: impl TelemetryHook + 'static) -> Option<Box<dyn TelemetryHook>> {
+    HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()).replace(Box::new(hook))
+}
+
+/// Uninstalls the current hook, returning it (if any).
+pub fn take_hook() -> Option<Box<dyn TelemetryHook>> {
+    HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()).take()
+}
+
+/// The stats for each container/type that has ever held a value.
+pub fn stats() -> Vec<ErasureStats> {
+    STATS.lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .values()
+        .copied()
+        .collect()
+}
+
+/// Reports the outstanding erasures (i.e. values which have been erased, but not yet downcast or
+/// dropped).
+pub fn report() -> TelemetryReport {
+    TelemetryReport {
+        outstanding: stats().into_iter().filter(|stats| stats.live > 0).collect()
+    }
+}
+
+/// Runs `f`, which creates, downcasts or drops the inner erasure (of type `E`) of a container,
+/// skipping the event recorded by the inner erasure, so that it is only recorded by the container.
+///
+/// The skipped event is the next one recorded by an `E` (see [record]), so instrumented erasures
+/// must record it before running any other code (e.g. the drop of the value, or a constructor's
+/// closure), as that could record events of other `E`s. Uninstrumented erasures never record one,
+/// so don't skip anything.
+pub(crate) fn outermost<E: ?Sized, R>(f: impl FnOnce() -> R) -> R {
+    /// Restores the inner erasure of any enclosing container, even if `f` panics.
+    struct Restore(Option<&'static str>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            INNER.set(self.0)
+        }
+    }
+
+    let _restore = Restore(INNER.replace(Some(type_name::<E>())));
+
+    f()
+}
+
+/// Records an event for a `size`-byte value of type `type_name`, held in `container` (an erasure
+/// of type `E`), unless it is the inner erasure of a container (see [outermost]).
+pub(crate) fn record<E: ?Sized>(
+    kind: TelemetryEventKind,
+    container: &'static str,
+    type_name: &'static str,
+    size: usize
+) {
+    if INNER.get() == Some(std::any::type_name::<E>()) {
+        INNER.set(None);
+
+        return;
+    }
+
+    let event = TelemetryEvent { kind, container, type_name, size };
+
+    STATS.lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry((container, type_name))
+        .or_insert(ErasureStats {
+            container,
+            type_name,
+            live: 0,
+            live_bytes: 0,
+            peak: 0,
+            peak_bytes: 0,
+            total: 0
+        })
+        .record(&event);
+
+    if let Some(hook) = HOOK.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_ref() {
+        hook.on_event(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use crate::erasure::{AutoDropUnique, ErasedBox, Erase, Erasure, InlineErasure};
+    use crate::erasure::telemetry::{report, set_hook, stats, take_hook, ErasureStats, TelemetryEventKind};
+
+    /// Finds the stats for values of `T` in the erasure named `container`.
+    fn stats_of<T>(container: &str) -> ErasureStats {
+        stats()
+            .into_iter()
+            .find(|stats| stats.container() == container && stats.type_name() == std::any::type_name::<T>())
+            .expect("values were erased")
+    }
+
+    #[test]
+    fn auto_drop_unique() {
+        struct Counted(#[allow(dead_code)] u64);
+
+        let first = AutoDropUnique::new(Counted(1)).erase();
+        let second = AutoDropUnique::new(Counted(2)).erase();
+
+        let stats = stats_of::<Counted>("AutoDropUnique");
+        assert_eq!(stats.live(), 2);
+        assert_eq!(stats.live_bytes(), 16);
+
+        drop(first);
+        let _unerased: Counted = unsafe { second.downcast_unchecked() };
+
+        let stats = stats_of::<Counted>("AutoDropUnique");
+        assert_eq!(stats.live(), 0);
+        assert_eq!(stats.peak(), 2);
+        assert_eq!(stats.peak_bytes(), 16);
+        assert_eq!(stats.total(), 2);
+    }
+
+    #[test]
+    fn inline_erasure_drop() {
+        struct Inline;
+
+        let erased: InlineErasure<8, 8> = Erase::erase(Inline);
+
+        let expected = format!("1 live InlineErasure<{}>", std::any::type_name::<Inline>());
+        assert!(report().to_string().contains(&expected));
+
+        drop(erased);
+
+        assert_eq!(stats_of::<Inline>("InlineErasure").live(), 0);
+    }
+
+    #[test]
+    fn outermost_container() {
+        struct Boxed(#[allow(dead_code)] u32);
+        struct Inline(#[allow(dead_code)] u32);
+
+        let boxed: ErasedBox = ErasedBox::new(Boxed(1));
+        let inline: ErasedBox<InlineErasure<8, 8>> = ErasedBox::new(Inline(2));
+
+        assert_eq!(stats_of::<Boxed>("ErasedBox").live(), 1);
+        assert_eq!(stats_of::<Inline>("ErasedBox").live(), 1);
+
+        drop(boxed);
+        let _unerased: Inline = unsafe { inline.downcast_unchecked() };
+
+        assert_eq!(stats_of::<Boxed>("ErasedBox").live(), 0);
+        assert_eq!(stats_of::<Inline>("ErasedBox").total(), 1);
+
+        // The underlying erasures are never counted
+        assert!(stats().iter().all(|stats| stats.container() == "ErasedBox"
+            || (stats.type_name() != std::any::type_name::<Boxed>()
+                && stats.type_name() != std::any::type_name::<Inline>())));
+    }
+
+    #[test]
+    fn uninstrumented_inner_erasure() {
+        struct Logged(#[allow(dead_code)] u32);
+
+        /// Holds its value directly, but also erases another value of the same type.
+        struct WithLog(Logged, #[allow(dead_code)] AutoDropUnique);
+
+        impl Erasure<Logged> for WithLog {
+            unsafe fn downcast_unchecked(self) -> Logged {
+                self.0
+            }
+        }
+
+        impl Erase<Logged> for WithLog {
+            fn erase(value: Logged) -> Self {
+                WithLog(value, AutoDropUnique::new(Logged(0)).erase())
+            }
+        }
+
+        let boxed: ErasedBox<WithLog> = ErasedBox::new(Logged(1));
+
+        // The other value isn't mistaken for the inner erasure
+        assert_eq!(stats_of::<Logged>("ErasedBox").live(), 1);
+        assert_eq!(stats_of::<Logged>("AutoDropUnique").live(), 1);
+
+        drop(boxed);
+
+        assert_eq!(stats_of::<Logged>("ErasedBox").live(), 0);
+        assert_eq!(stats_of::<Logged>("AutoDropUnique").live(), 0);
+    }
+
+    #[test]
+    fn inline_erasure_constructors() {
+        struct Constant;
+
+        let erased: InlineErasure<8, 8> = InlineErasure::new(Constant);
+        drop(erased);
+
+        assert!(stats().iter().all(|stats| stats.type_name() != std::any::type_name::<Constant>()));
+
+        let erased: InlineErasure<8, 8> = InlineErasure::new_with(|| Constant);
+
+        assert_eq!(stats_of::<Constant>("InlineErasure").live(), 1);
+
+        drop(erased);
+
+        assert_eq!(stats_of::<Constant>("InlineErasure").live(), 0);
+    }
+
+    #[test]
+    fn hook() {
+        struct Hooked;
+
+        static EVENTS: Mutex<Vec<TelemetryEventKind>> = Mutex::new(Vec::new());
+
+        set_hook(|event: &crate::erasure::telemetry::TelemetryEvent| {
+            if event.type_name() == std::any::type_name::<Hooked>() {
+                EVENTS.lock().unwrap().push(event.kind());
+            }
+        });
+
+        let erased: ErasedBox = ErasedBox::new(Hooked);
+        let _unerased: Hooked = unsafe { erased.downcast_unchecked() };
+
+        take_hook();
+
+        assert_eq!(
+            *EVENTS.lock().unwrap(),
+            [TelemetryEventKind::Erased, TelemetryEventKind::Downcast]
+        );
+    }
+}
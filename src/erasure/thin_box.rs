@@ -0,0 +1,408 @@
+use std::alloc::handle_alloc_error;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::mem::{forget, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::ptr::{addr_of_mut, NonNull};
+use crate::erasure::{AllocError, AutoTraitsOf, Erasure, ErasureMut, ErasureRef, Unknown};
+use crate::util::{try_box_uninit, with_data_pointer, CPair};
+#[cfg(feature = "verify-types")]
+use std::any::TypeId;
+#[cfg(feature = "verify-types")]
+use crate::erasure::type_verification::{type_info, verify_type_info};
+
+/// An owning pointer to a trait-object (or other unsized type) on the heap, which is a single
+/// word in size.
+///
+/// Where `Box<dyn Trait>` holds the vtable in the pointer itself, `ThinBox<dyn Trait>` holds it
+/// in a header alongside the value on the heap, in the same way that [AutoDropUnique] holds the
+/// `drop` implementation of its value.
+///
+/// As stable Rust has no generic unsizing coercion, the coercion from the value to `Dyn` is
+/// supplied as a closure on creation, e.g.:
+/// ```
+/// use std::fmt::Display;
+/// use dynrsaur::erasure::ThinBox;
+///
+/// let thin: ThinBox<dyn Display> = ThinBox::new(42, |x| x as &dyn Display);
+///
+/// assert_eq!(thin.to_string(), "42");
+/// assert_eq!(size_of_val(&thin), size_of::<usize>());
+/// ```
+///
+/// As the coercion could return a field of the value (whose auto traits may differ from those of
+/// the value), the auto traits of `Dyn` are ignored, and the `ThinBox` is only `Send`/`Sync` if
+/// `Auto` is, which requires the value to be too:
+/// ```
+/// use std::fmt::Debug;
+/// use std::thread;
+/// use dynrsaur::erasure::{ThinBox, Unknown};
+///
+/// let thin: ThinBox<dyn Debug, dyn Unknown + Send> = ThinBox::new(42, |x| x as &dyn Debug);
+///
+/// thread::spawn(move || println!("{:?}", thin)).join().unwrap();
+/// ```
+/// ```compile_fail
+/// use std::fmt::Debug;
+/// use std::marker::PhantomData;
+/// use dynrsaur::erasure::{ThinBox, Unknown};
+///
+/// #[derive(Debug)]
+/// struct NotSend {
+///     id: u64,
+///     _not_send: PhantomData<*const ()>
+/// }
+///
+/// // The field is `Send`, but the value isn't
+/// let thin: ThinBox<dyn Debug + Send, dyn Unknown + Send> = ThinBox::new(
+///     NotSend { id: 0, _not_send: PhantomData },
+///     |x| &x.id as &(dyn Debug + Send)
+/// );
+/// ```
+///
+/// Generic Parameters:
+/// - `Dyn`: the (usually unsized) type the value is accessed as.
+/// - `Auto`: the [auto traits](AutoTraitsOf) of the value, as `dyn Unknown` with any of `Send`
+///   and `Sync`.
+///
+/// [AutoDropUnique]: crate::erasure::AutoDropUnique
+pub struct ThinBox<Dyn: ?Sized, Auto: ?Sized = dyn Unknown> {
+    /// Pointer to the header/value pair on the heap (see [ThinBoxInner]).
+    inner: NonNull<ThinBoxHeader<Dyn>>,
+    /// Marker indicating that we own a `Dyn`, with the auto traits of `Auto`.
+    _marker: PhantomData<(PhantomData<Dyn>, PhantomData<Auto>)>
+}
+
+// SAFETY: `ThinBox<Dyn, Auto>` owns its value in the same way as `Box<T>`, and the value has the
+//         auto traits of `Auto` (which are checked on creation).
+unsafe impl<Dyn: ?Sized, Auto: ?Sized + Send> Send for ThinBox<Dyn, Auto> {}
+unsafe impl<Dyn: ?Sized, Auto: ?Sized + Sync> Sync for ThinBox<Dyn, Auto> {}
+
+impl<Dyn: ?Sized, Auto: ?Sized> ThinBox<Dyn, Auto> {
+    /// Takes ownership of `value`, accessing it as a `Dyn` via `coerce`.
+    ///
+    /// This always allocates, as the header must be stored even if `T` is a ZST.
+    ///
+    /// Panics if `coerce` doesn't return the reference it was given (e.g. if it returns another
+    /// value, or a non-zero-sized field of the value).
+    pub fn new<T>(
+        value: T,
+        coerce: impl for<'a> FnOnce(&'a T) -> &'a Dyn
+    ) -> Self where Auto: AutoTraitsOf<T> {
+        Self::try_new(value, coerce).unwrap_or_else(|error| handle_alloc_error(error.layout()))
+    }
+
+    /// Takes ownership of `value`, as [ThinBox::new], but returns an error (dropping the value)
+    /// instead of aborting if the allocation fails.
+    ///
+    /// E.g. with an [AlignedArena](crate::align::AlignedArena) as the global allocator:
+    /// ```
+    /// use std::fmt::Debug;
+    /// use dynrsaur::align::AlignedArena;
+    /// use dynrsaur::erasure::ThinBox;
+    ///
+    /// #[global_allocator]
+    /// static ARENA: AlignedArena<{ 1 << 16 }, 4096> = AlignedArena::new();
+    ///
+    /// assert!(ThinBox::<dyn Debug>::try_new([0u8; 1024], |x| x as &dyn Debug).is_ok());
+    ///
+    /// let error = ThinBox::<dyn Debug>::try_new([0u8; 1 << 16], |x| x as &dyn Debug).unwrap_err();
+    /// assert!(error.layout().size() > 1 << 16);
+    /// ```
+    pub fn try_new<T>(
+        value: T,
+        coerce: impl for<'a> FnOnce(&'a T) -> &'a Dyn
+    ) -> Result<Self, AllocError> where Auto: AutoTraitsOf<T> {
+        // Allocated before the value is moved in, so that it is dropped if the allocation fails
+        let mut inner = try_box_uninit::<ThinBoxInner<Dyn, T>>()?;
+
+        inner.write(
+            CPair(
+                ThinBoxHeader {
+                    // Placeholder until the value has its final address
+                    object: None,
+                    drop: drop_impl::<Dyn, T>,
+                    #[cfg(feature = "verify-types")]
+                    type_info: type_info::<T>()
+                },
+                ManuallyDrop::new(value)
+            )
+        );
+
+        let inner_ptr = Box::into_raw(inner) as *mut ThinBoxInner<Dyn, T>;
+
+        // SAFETY: `inner_ptr` is from a Box (so valid and initialised), and is uniquely owned.
+        unsafe {
+            let value = addr_of_mut!((*inner_ptr).1) as *mut T;
+
+            let object = coerce(&*value) as *const Dyn as *mut Dyn;
+
+            // A field at the start of the value has the same address, but (unless it is the
+            // whole value) a smaller size
+            assert!(
+                object as *mut u8 == value as *mut u8 && size_of_val(&*object) == size_of::<T>(),
+                "coercion must return the reference it was given"
+            );
+
+            // Give the object pointer the provenance of the (mutable) value pointer
+            let object = with_data_pointer(object, value as *mut u8);
+
+            (*inner_ptr).0.object = Some(NonNull::new_unchecked(object));
+        }
+
+        Ok(ThinBox {
+            // SAFETY: From a Box, so non-null.
+            inner: unsafe { NonNull::new_unchecked(inner_ptr).cast() },
+            _marker: PhantomData
+        })
+    }
+
+    fn header(&self) -> &ThinBoxHeader<Dyn> {
+        // SAFETY: The header is at the start of the `#[repr(C)]` pair.
+        unsafe { self.inner.as_ref() }
+    }
+
+    /// Returns the pointer to the value, with its metadata.
+    fn object(&self) -> NonNull<Dyn> {
+        self.header().object.expect("object pointer is set on creation")
+    }
+
+    /// Panics if the value is not a `T`.
+    #[cfg(feature = "verify-types")]
+    fn verify_type<T>(&self) {
+        verify_type_info::<T>(self.header().type_info)
+    }
+
+    /// Returns a pointer to the value, assuming it is a `T`.
+    ///
+    /// # Safety
+    ///
+    /// The value must be a `T`.
+    unsafe fn as_ptr<T>(&self) -> *mut T {
+        let inner_ptr = self.inner.cast::<ThinBoxInner<Dyn, T>>().as_ptr();
+        addr_of_mut!((*inner_ptr).1) as *mut T
+    }
+}
+
+impl<Dyn: ?Sized, Auto: ?Sized> Deref for ThinBox<Dyn, Auto> {
+    type Target = Dyn;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The object pointer is valid for the lifetime of the ThinBox.
+        unsafe { self.object().as_ref() }
+    }
+}
+
+impl<Dyn: ?Sized, Auto: ?Sized> DerefMut for ThinBox<Dyn, Auto> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The object pointer is valid for the lifetime of the ThinBox, and we hold
+        //         `&mut self`.
+        unsafe { self.object().as_mut() }
+    }
+}
+
+impl<Dyn: ?Sized, Auto: ?Sized> Drop for ThinBox<Dyn, Auto> {
+    fn drop(&mut self) {
+        let drop = self.header().drop;
+
+        // SAFETY: `drop` was created for the type of the value, and is only called once.
+        unsafe { drop(self.inner.cast()) }
+    }
+}
+
+impl<Dyn: ?Sized + Debug, Auto: ?Sized> Debug for ThinBox<Dyn, Auto> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+impl<Dyn: ?Sized + Display, Auto: ?Sized> Display for ThinBox<Dyn, Auto> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.deref(), f)
+    }
+}
+
+impl<Dyn: ?Sized, Auto: ?Sized, T> Erasure<T> for ThinBox<Dyn, Auto> {
+    unsafe fn downcast_unchecked(self) -> T {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+
+        let inner = self.inner.cast::<ThinBoxInner<Dyn, T>>();
+
+        forget(self);
+
+        // SAFETY: created by Box::into_raw
+        let CPair(_header, value) = *Box::from_raw(inner.as_ptr());
+
+        ManuallyDrop::into_inner(value)
+    }
+}
+
+impl<Dyn: ?Sized, Auto: ?Sized> ErasureRef for ThinBox<Dyn, Auto> {
+    unsafe fn downcast_ref_unchecked<T>(&self) -> &T {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+
+        &*self.as_ptr::<T>()
+    }
+}
+
+impl<Dyn: ?Sized, Auto: ?Sized> ErasureMut for ThinBox<Dyn, Auto> {
+    unsafe fn downcast_mut_unchecked<T>(&mut self) -> &mut T {
+        #[cfg(feature = "verify-types")]
+        self.verify_type::<T>();
+
+        &mut *self.as_ptr::<T>()
+    }
+}
+
+/// The information about the value of a [ThinBox] stored alongside it on the heap.
+struct ThinBoxHeader<Dyn: ?Sized> {
+    /// Pointer to the value in the same allocation, holding its metadata (e.g. its vtable).
+    object: Option<NonNull<Dyn>>,
+    /// Pointer to [drop_impl].
+    drop: unsafe fn(NonNull<()>),
+    /// The [type information](type_info) of the value, to verify downcasts.
+    #[cfg(feature = "verify-types")]
+    type_info: (TypeId, &'static str)
+}
+
+/// The heap data-structure held by [ThinBox].
+///
+/// A C-like pair of:
+/// 1. the [header](ThinBoxHeader) for the value, and,
+/// 2. the value itself.
+type ThinBoxInner<Dyn, T> = CPair<ThinBoxHeader<Dyn>, ManuallyDrop<T>>;
+
+/// Implementation of drop which remembers the type of the value.
+///
+/// # Safety
+///
+/// Must only be called when dropping the [ThinBox], and `inner` must be the [ThinBoxInner] of
+/// a `T`.
+unsafe fn drop_impl<Dyn: ?Sized, T>(inner: NonNull<()>) {
+    let mut ptr = inner.cast::<ThinBoxInner<Dyn, T>>();
+
+    // Extract the value from the inner pair and drop it
+    ManuallyDrop::drop(&mut ptr.as_mut().1);
+
+    // Drop the heap memory
+    drop(Box::from_raw(ptr.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::fmt::{Debug, Display};
+    use crate::erasure::{Erasure, ThinBox};
+
+    trait Counter {
+        fn increment(&mut self);
+        fn count(&self) -> usize;
+    }
+
+    impl Counter for usize {
+        fn increment(&mut self) {
+            *self += 1;
+        }
+
+        fn count(&self) -> usize {
+            *self
+        }
+    }
+
+    #[test]
+    fn single_word_size() {
+        assert_eq!(size_of::<ThinBox<dyn Display>>(), size_of::<usize>());
+        assert_eq!(size_of::<ThinBox<dyn Counter>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<ThinBox<[u8]>>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn deref() {
+        let thin: ThinBox<dyn Debug> = ThinBox::new(String::from("test"), |x| x as &dyn Debug);
+
+        assert_eq!(format!("{:?}", thin), "\"test\"");
+    }
+
+    #[test]
+    fn deref_mut() {
+        let mut thin: ThinBox<dyn Counter> = ThinBox::new(0usize, |x| x as &dyn Counter);
+
+        thin.increment();
+        thin.increment();
+
+        assert_eq!(thin.count(), 2);
+    }
+
+    #[test]
+    fn slice() {
+        let thin: ThinBox<[u32]> = ThinBox::new([1u32, 2, 3], |x| x as &[u32]);
+
+        assert_eq!(&*thin, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn zst() {
+        let thin: ThinBox<dyn Debug> = ThinBox::new((), |x| x as &dyn Debug);
+
+        assert_eq!(format!("{:?}", thin), "()");
+    }
+
+    #[test]
+    fn readback() {
+        let thin: ThinBox<dyn Display> = ThinBox::new(String::from("test"), |x| x as &dyn Display);
+
+        let unerased: String = unsafe { thin.downcast_unchecked() };
+
+        assert_eq!(unerased, "test");
+    }
+
+    #[test]
+    #[should_panic]
+    fn coercion_to_other_value_panics() {
+        static OTHER: usize = 0;
+
+        let _thin: ThinBox<dyn Counter> = ThinBox::new(1usize, |_| &OTHER as &dyn Counter);
+    }
+
+    #[test]
+    #[should_panic]
+    fn coercion_to_field_panics() {
+        use std::rc::Rc;
+
+        // The field at the start of the value has the same address as the value
+        #[repr(C)]
+        struct Pair(usize, Rc<()>);
+
+        let _thin: ThinBox<dyn Debug> = ThinBox::new(Pair(0, Rc::new(())), |x| &x.0 as &dyn Debug);
+    }
+
+    #[test]
+    fn erased_drop() {
+        let dropped = Cell::new(false);
+
+        struct SetTrueOnDrop<'a>(&'a Cell<bool>);
+
+        impl Drop for SetTrueOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        trait Nothing {}
+
+        impl Nothing for SetTrueOnDrop<'_> {}
+
+        {
+            let _thin: ThinBox<dyn Nothing> = ThinBox::new(
+                SetTrueOnDrop(&dropped),
+                |x| x as &dyn Nothing
+            );
+
+            assert!(!dropped.get());
+        }
+
+        assert!(dropped.get());
+    }
+}
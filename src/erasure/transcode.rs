@@ -0,0 +1,249 @@
+use std::alloc::Layout;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::ptr::NonNull;
+use sealed::sealed;
+use crate::erasure::Unknown;
+
+/// Conversion of an [untagged erasure](crate::erasure::Erasure) into another kind of erasure,
+/// without knowing the type of the underlying value.
+///
+/// The value is moved (bitwise) into the target, using the layout and drop glue recorded by the
+/// source erasure (so, as for the erasures themselves, the lifetime of the value is not tracked).
+/// Implemented between [AutoDropUnique](crate::erasure::AutoDropUnique),
+/// [InlineErasure](crate::erasure::InlineErasure) and
+/// [`Box<dyn Unknown>`](crate::erasure::Unknown) (only for `'static` boxes, as the other erasures
+/// don't track the lifetime of the value), e.g.:
+/// ```
+/// use dynrsaur::erasure::{AutoDropUnique, Erasure, InlineErasure, Transcode};
+///
+/// let inline: InlineErasure<24, 8> = InlineErasure::new(String::from("test"));
+///
+/// let boxed: AutoDropUnique = inline.transcode().expect("heap has room for a String");
+///
+/// let string: String = unsafe { boxed.downcast_unchecked() };
+/// assert_eq!(string, "test");
+/// ```
+///
+/// Transcoding a box of a borrowing value would discard its lifetime, so isn't implemented:
+/// ```compile_fail
+/// use dynrsaur::erasure::{AutoDropUnique, Transcode, Unknown};
+///
+/// let string = String::from("test");
+/// let boxed: Box<dyn Unknown + '_> = Box::new(&string);
+///
+/// let unique: AutoDropUnique = boxed.transcode().unwrap();
+/// ```
+///
+/// Generic Parameters:
+/// - `Target`: the kind of erasure to convert to.
+pub trait Transcode<Target>: Sized {
+    /// Moves the underlying value into a `Target` erasure, or returns an error (holding this
+    /// erasure) if the target can't hold it.
+    fn transcode(self) -> Result<Target, TranscodeError<Self>>;
+}
+
+/// Erasure which the underlying value can be [transcoded](Transcode) out of.
+#[sealed(pub(in super))]
+#[doc(hidden)]
+pub trait TranscodeSource: Sized {
+    /// Returns a pointer to the underlying value, with its metadata.
+    fn object(&self) -> NonNull<dyn Unknown>;
+
+    /// Releases the erasure without dropping the underlying value.
+    ///
+    /// # Safety
+    ///
+    /// The value must have been moved out of the erasure.
+    unsafe fn forget_value(self);
+}
+
+/// Erasure which a value can be [transcoded](Transcode) into.
+#[sealed(pub(in super))]
+#[doc(hidden)]
+pub trait TranscodeTarget: Sized {
+    /// Moves the value (bitwise) from `object` into a new erasure. The value is left untouched
+    /// on failure.
+    ///
+    /// # Safety
+    ///
+    /// `object` must point to a valid value, which is treated as moved on success.
+    unsafe fn read_from(object: NonNull<dyn Unknown>) -> Result<Self, TranscodeErrorKind>;
+}
+
+impl<Source: TranscodeSource, Target: TranscodeTarget> Transcode<Target> for Source {
+    fn transcode(self) -> Result<Target, TranscodeError<Self>> {
+        let object = self.object();
+
+        // SAFETY: The object is the valid underlying value of the source erasure, which is
+        //         released without dropping it if the value is moved.
+        match unsafe { Target::read_from(object) } {
+            Ok(target) => {
+                // SAFETY: Moved into `target` above.
+                unsafe { self.forget_value() };
+
+                Ok(target)
+            },
+            Err(kind) => {
+                // SAFETY: The value is still held by the source erasure.
+                let layout = Layout::for_value(unsafe { object.as_ref() });
+
+                Err(TranscodeError { erasure: self, layout, kind })
+            }
+        }
+    }
+}
+
+/// Why a [transcode](Transcode) failed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TranscodeErrorKind {
+    /// The value is bigger than the target can hold.
+    TooBig {
+        /// The size of the biggest value the target can hold.
+        max: usize
+    },
+    /// The value is more aligned than the target can hold.
+    TooAligned {
+        /// The alignment of the most aligned value the target can hold.
+        max: usize
+    },
+    /// The target failed to allocate.
+    Alloc
+}
+
+/// Error returned when an erasure can't be [transcoded](Transcode) into the target erasure.
+///
+/// Holds the original erasure, so that it can be [reclaimed](TranscodeError::into_erasure).
+///
+/// Generic Parameters:
+/// - `E`: the type of the source erasure.
+pub struct TranscodeError<E> {
+    /// The erasure that failed to transcode.
+    erasure: E,
+    /// The layout of the underlying value.
+    layout: Layout,
+    /// Why the transcode failed.
+    kind: TranscodeErrorKind
+}
+
+impl<E> TranscodeError<E> {
+    /// The layout of the underlying value.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Why the transcode failed.
+    pub fn kind(&self) -> TranscodeErrorKind {
+        self.kind
+    }
+
+    /// Borrows the erasure that failed to transcode.
+    pub fn erasure(&self) -> &E {
+        &self.erasure
+    }
+
+    /// Reclaims the erasure that failed to transcode.
+    pub fn into_erasure(self) -> E {
+        self.erasure
+    }
+}
+
+impl<E> Display for TranscodeError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "can't transcode a value of {} bytes (aligned to {}): ",
+            self.layout.size(),
+            self.layout.align()
+        )?;
+
+        match self.kind {
+            TranscodeErrorKind::TooBig { max } => write!(f, "target holds at most {} bytes", max),
+            TranscodeErrorKind::TooAligned { max } => write!(f, "target is only aligned to {}", max),
+            TranscodeErrorKind::Alloc => write!(f, "allocation failed")
+        }
+    }
+}
+
+impl<E> Debug for TranscodeError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranscodeError")
+            .field("layout", &self.layout)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> Error for TranscodeError<E> {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use crate::erasure::{AutoDropUnique, Erasure, InlineErasure, Transcode, TranscodeErrorKind, Unknown};
+
+    #[test]
+    fn round_trip() {
+        let inline: InlineErasure<24, 8> = InlineErasure::new(String::from("test"));
+
+        let unique: AutoDropUnique = inline.transcode().unwrap();
+        let boxed: Box<dyn Unknown> = unique.transcode().unwrap();
+        let inline: InlineErasure<32, 16> = boxed.transcode().unwrap();
+
+        let string: String = unsafe { inline.downcast_unchecked() };
+        assert_eq!(string, "test");
+    }
+
+    #[test]
+    fn zst() {
+        let unique = AutoDropUnique::new(()).erase();
+
+        let inline: InlineErasure<0, 1> = unique.transcode().unwrap();
+        let unique: AutoDropUnique = inline.transcode().unwrap();
+        let boxed: Box<dyn Unknown> = unique.transcode().unwrap();
+
+        let _unit: () = unsafe { boxed.downcast_unchecked() };
+    }
+
+    #[test]
+    fn too_small() {
+        let unique = AutoDropUnique::new([0u64; 4]).erase();
+
+        let error = Transcode::<InlineErasure<16, 8>>::transcode(unique).err().expect("too big");
+        assert_eq!(error.kind(), TranscodeErrorKind::TooBig { max: 16 });
+        assert_eq!(
+            error.to_string(),
+            "can't transcode a value of 32 bytes (aligned to 8): target holds at most 16 bytes"
+        );
+
+        let error = Transcode::<InlineErasure<32, 4>>::transcode(error.into_erasure()).err().expect("too aligned");
+        assert_eq!(error.kind(), TranscodeErrorKind::TooAligned { max: 4 });
+
+        let array: [u64; 4] = unsafe { error.into_erasure().downcast_unchecked() };
+        assert_eq!(array, [0; 4]);
+    }
+
+    #[test]
+    fn dropped_once() {
+        let drops = Rc::new(Cell::new(0));
+
+        struct CountDrops(Rc<Cell<usize>>);
+
+        impl Drop for CountDrops {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        {
+            let unique = AutoDropUnique::new(CountDrops(drops.clone())).erase();
+
+            let inline: InlineErasure<8, 8> = unique.transcode().unwrap();
+            let _boxed: Box<dyn Unknown> = inline.transcode().unwrap();
+
+            assert_eq!(drops.get(), 0);
+        }
+
+        assert_eq!(drops.get(), 1);
+    }
+}
@@ -0,0 +1,64 @@
+use std::any::Any;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use crate::erasure::{AllocError, Erasure};
+use crate::for_all_combinations;
+use crate::util::try_box;
+
+/// [Erasure]-type which can be constructed by taking ownership of a value, where the construction
+/// may fail to allocate.
+/// 
+/// The fallible counterpart to [Erase](crate::erasure::Erase).
+/// 
+/// Generic Parameters:
+/// - `T`: The underlying erased type.
+pub trait TryErase<T>: Erasure<T> + Sized {
+    /// Creates an instance of this type of [erasure](Erasure) from a value of type `T`, or returns
+    /// an error (dropping the value) if the required allocation fails.
+    fn try_erase(value: T) -> Result<Self, AllocError>;
+}
+
+macro_rules! impl_try_erase_for_any {
+    ($($traits:ident),*) => {
+        impl<T: 'static$( + $traits)*> TryErase<T> for Box<dyn Any$( + $traits)*> {
+            fn try_erase(value: T) -> Result<Self, AllocError> {
+                try_box(value).map(|boxed| boxed as Box<dyn Any$( + $traits)*>)
+            }
+        }
+    };
+}
+
+for_all_combinations!(impl_try_erase_for_any => Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::Layout;
+    use std::any::Any;
+    use crate::erasure::{AllocError, AutoDropUnique, ErasedBox, ErasedIter, InlineErasure, TryErase, Unknown};
+
+    #[test]
+    fn try_erase() {
+        fn readback<E: TryErase<String>>() {
+            let erased = E::try_erase(String::from("test")).expect("allocation succeeds");
+            
+            let unerased: String = unsafe { erased.downcast_unchecked() };
+            
+            assert_eq!(unerased, "test");
+        }
+        
+        readback::<AutoDropUnique>();
+        readback::<InlineErasure<24, 8>>();
+        readback::<Box<dyn Any>>();
+        readback::<Box<dyn Unknown + Send>>();
+        readback::<ErasedBox>();
+        
+        let erased = ErasedIter::try_erase(0..3).expect("allocation succeeds");
+        assert_eq!(erased.collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn alloc_error_display() {
+        let error = AllocError::new(Layout::new::<u64>());
+        
+        assert_eq!(error.to_string(), "memory allocation of 8 bytes (aligned to 8) failed");
+    }
+}
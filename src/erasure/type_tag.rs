@@ -0,0 +1,25 @@
+use std::any::{type_name, TypeId};
+
+/// A single-word tag identifying a (`'static`) type, for use in [tagged erasures](crate::erasure::TaggedErasure).
+/// 
+/// [TypeId] is more than a word in size, so instead this holds a pointer to a function which
+/// generates the [TypeId] (and [type_name]) of the tagged type.
+#[derive(Copy, Clone)]
+pub(crate) struct TypeTag(fn() -> (TypeId, &'static str));
+
+impl TypeTag {
+    /// Creates the tag for `T`.
+    pub fn of<T: ?Sized + 'static>() -> Self {
+        TypeTag(|| (TypeId::of::<T>(), type_name::<T>()))
+    }
+    
+    /// Checks if this is the tag for `T`.
+    pub fn is<T: ?Sized + 'static>(&self) -> bool {
+        (self.0)().0 == TypeId::of::<T>()
+    }
+    
+    /// The [name](type_name) of the tagged type.
+    pub fn type_name(&self) -> &'static str {
+        (self.0)().1
+    }
+}
@@ -0,0 +1,134 @@
+use std::any::{type_name, TypeId};
+use std::marker::PhantomData;
+use std::mem::transmute;
+
+/// The [TypeId] (ignoring lifetimes) and [type_name] of `T`, used to verify downcasts of
+/// [untagged erasures](crate::erasure::Erasure) when the `verify-types` feature is enabled.
+/// 
+/// Unlike [TypeTag](crate::erasure::type_tag::TypeTag), the type need not be `'static`, as
+/// lifetimes are ignored when comparing types.
+pub(crate) fn type_info<T: ?Sized>() -> (TypeId, &'static str) {
+    /// Object-safe way to get the [TypeId] of a type with its lifetimes erased.
+    trait NonStaticAny {
+        fn type_id(&self) -> TypeId where Self: 'static;
+    }
+    
+    impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+        fn type_id(&self) -> TypeId where Self: 'static {
+            TypeId::of::<T>()
+        }
+    }
+    
+    let phantom = PhantomData::<T>;
+    
+    // SAFETY: Lifetimes are erased before code generation, so the `'static` version of the vtable
+    //         is the same as the original, and `phantom` is a ZST so there is nothing to outlive.
+    let phantom = unsafe {
+        transmute::<&dyn NonStaticAny, &(dyn NonStaticAny + 'static)>(&phantom)
+    };
+    
+    (phantom.type_id(), type_name::<T>())
+}
+
+/// Panics (with both type names) if `actual` is not the [type information](type_info) of `T`.
+pub(crate) fn verify_type_info<T: ?Sized>((actual_id, actual_name): (TypeId, &'static str)) {
+    let (expected_id, expected_name) = type_info::<T>();
+    
+    if actual_id != expected_id {
+        panic!(
+            "type verification failed: downcast to '{}', but the underlying type is '{}'",
+            expected_name,
+            actual_name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::ops::Deref;
+    use crate::erasure::{
+        ErasedIter, Erasure, ErasureMut, ErasureRef, InlineDyn, InlineErasure, ThinBox, Unknown
+    };
+    use crate::erasure::type_verification::{type_info, verify_type_info};
+
+    #[test]
+    fn ignores_lifetimes() {
+        let string = String::from("test");
+        
+        fn type_info_of<T>(_value: &T) -> (std::any::TypeId, &'static str) {
+            type_info::<T>()
+        }
+        
+        verify_type_info::<&'static str>(type_info_of(&string.as_str()));
+    }
+
+    #[test]
+    #[should_panic(expected = "downcast to 'u32', but the underlying type is 'alloc::string::String'")]
+    fn mismatch_panics() {
+        verify_type_info::<u32>(type_info::<String>());
+    }
+
+    #[test]
+    #[should_panic(expected = "downcast to 'i64', but the underlying type is 'u64'")]
+    fn inline_erasure_mismatch_panics() {
+        let erased: InlineErasure<8, 8> = InlineErasure::new(42u64);
+
+        let _unerased: i64 = unsafe { erased.downcast_unchecked() };
+    }
+
+    #[test]
+    #[should_panic(expected = "downcast to 'u32', but the underlying type is 'alloc::string::String'")]
+    fn unknown_mismatch_panics() {
+        let erased: Box<dyn Unknown> = Box::new(String::from("test"));
+
+        let _unerased: &u32 = unsafe { erased.deref().downcast_unchecked() };
+    }
+
+    #[test]
+    #[should_panic(expected = "downcast to 'i32', but the underlying type is 'u32'")]
+    fn thin_box_mismatch_panics() {
+        let erased: ThinBox<dyn Debug> = ThinBox::new(42u32, |x| x as &dyn Debug);
+
+        let _unerased: &i32 = unsafe { erased.downcast_ref_unchecked() };
+    }
+
+    #[test]
+    #[should_panic(expected = "downcast to 'i32', but the underlying type is 'u32'")]
+    fn inline_dyn_mismatch_panics() {
+        let mut erased: InlineDyn<dyn Debug, 8, 8> = InlineDyn::new(42u32, |x| x as &dyn Debug);
+
+        let _unerased: &mut i32 = unsafe { erased.downcast_mut_unchecked() };
+    }
+
+    #[test]
+    #[should_panic(expected = "downcast to 'core::ops::range::Range<u8>'")]
+    fn erased_iter_mismatch_panics() {
+        let erased = ErasedIter::new(vec![1u8, 2, 3].into_iter());
+
+        let _unerased: std::ops::Range<u8> = unsafe { erased.downcast_unchecked() };
+    }
+
+    #[test]
+    fn matching_downcasts_succeed() {
+        let erased: InlineErasure<8, 8> = InlineErasure::new(42u64);
+        let unerased: u64 = unsafe { erased.downcast_unchecked() };
+        assert_eq!(unerased, 42);
+
+        let erased: Box<dyn Unknown> = Box::new(String::from("test"));
+        let unerased: String = unsafe { erased.downcast_unchecked() };
+        assert_eq!(unerased, "test");
+
+        let erased: ThinBox<dyn Debug> = ThinBox::new(42u32, |x| x as &dyn Debug);
+        let unerased: u32 = unsafe { erased.downcast_unchecked() };
+        assert_eq!(unerased, 42);
+
+        let erased: InlineDyn<dyn Debug, 8, 8> = InlineDyn::new(42u32, |x| x as &dyn Debug);
+        let unerased: u32 = unsafe { erased.downcast_unchecked() };
+        assert_eq!(unerased, 42);
+
+        let erased = ErasedIter::new(1u8..4);
+        let unerased: std::ops::Range<u8> = unsafe { erased.downcast_unchecked() };
+        assert_eq!(unerased, 1..4);
+    }
+}
@@ -1,26 +1,100 @@
-use std::alloc::{dealloc, Layout};
+use std::alloc::{alloc, dealloc, Layout};
+use std::mem::transmute;
 use std::panic::{UnwindSafe, RefUnwindSafe};
-use std::ptr::read;
-use crate::erasure::{Erase, Erasure};
+use std::ptr::{copy_nonoverlapping, read, without_provenance_mut, NonNull};
+use sealed::sealed;
+use crate::erasure::{AllocError, Erase, Erasure, TranscodeErrorKind, TryErase};
 use crate::for_all_combinations;
+use crate::util::{try_box, with_data_pointer};
+#[cfg(feature = "verify-types")]
+use std::any::TypeId;
+#[cfg(feature = "verify-types")]
+use crate::erasure::type_verification::{type_info, verify_type_info};
 
 /// Trait implemented for all types, providing no information about them.
-pub trait Unknown {}
+/// 
+/// With the `verify-types` feature, the vtable also provides the type of the value, so that
+/// downcasts can be verified.
+pub trait Unknown {
+    /// The [TypeId] (ignoring lifetimes) and [type name](std::any::type_name) of the value.
+    #[cfg(feature = "verify-types")]
+    #[doc(hidden)]
+    fn __type_info(&self) -> (TypeId, &'static str);
+}
+
+impl<T: ?Sized> Unknown for T {
+    #[cfg(feature = "verify-types")]
+    fn __type_info(&self) -> (TypeId, &'static str) {
+        type_info::<T>()
+    }
+}
+
+/// Returns a dangling (but aligned) pointer to a `T`, as a `dyn Unknown`.
+/// 
+/// The metadata (vtable) of the pointer provides the [layout](Layout::for_value) and drop glue
+/// (and, with the `verify-types` feature, the type) of `T`, for erasures which need them without
+/// knowing `T`. Use [with_data_pointer](crate::util::with_data_pointer) to point it at a value.
+pub(crate) const fn metadata_of<T>() -> NonNull<dyn Unknown> {
+    const fn unsize<'lifetime, T: 'lifetime>(ptr: NonNull<T>) -> NonNull<dyn Unknown + 'lifetime> {
+        ptr
+    }
+    
+    // SAFETY: Lifetimes are erased before code generation, so the vtable is the same for any
+    //         lifetime. Erasures must already not access values beyond their lifetimes.
+    unsafe {
+        transmute::<NonNull<dyn Unknown + '_>, NonNull<dyn Unknown>>(unsize(NonNull::<T>::dangling()))
+    }
+}
+
+/// The metadata (vtable) of a pointer to a value, as a `dyn Unknown`, without the pointer itself.
+/// 
+/// For erasures which hold values (of any type) at addresses of their own choosing (see
+/// [metadata_of]).
+#[derive(Copy, Clone)]
+pub(crate) struct UnknownMetadata(NonNull<dyn Unknown>);
+
+impl UnknownMetadata {
+    /// The metadata of a `T`.
+    pub const fn of<T>() -> Self {
+        UnknownMetadata(metadata_of::<T>())
+    }
+    
+    /// The metadata of the value `object` points to.
+    pub fn of_object(object: NonNull<dyn Unknown>) -> Self {
+        UnknownMetadata(object)
+    }
+    
+    /// Returns a pointer to the value at `data`, with this metadata.
+    pub fn with_data(self, data: NonNull<u8>) -> NonNull<dyn Unknown> {
+        // SAFETY: `data` is not null.
+        unsafe { NonNull::new_unchecked(with_data_pointer(self.0.as_ptr(), data.as_ptr())) }
+    }
+}
 
-impl<T: ?Sized> Unknown for T {}
+// SAFETY: The pointer is only used for its metadata, and is never dereferenced.
+unsafe impl Send for UnknownMetadata {}
+unsafe impl Sync for UnknownMetadata {}
+impl UnwindSafe for UnknownMetadata {}
+impl RefUnwindSafe for UnknownMetadata {}
 
 macro_rules! impl_erase_traits_for_unknown {
     ($($traits:ident),*) => {
         impl<'lifetime, T: 'lifetime$( + $traits)*> Erasure<T> for Box<dyn Unknown$( + $traits)* + 'lifetime> {
             unsafe fn downcast_unchecked(self) -> T {
+                #[cfg(feature = "verify-types")]
+                verify_type_info::<T>((*self).__type_info());
+                
                 let ptr = Box::into_raw(self);
         
                 let value = read(ptr as *const T);
         
-                dealloc(
-                    ptr as *mut u8,
-                    Layout::new::<T>()
-                );
+                // Boxes of ZSTs don't allocate
+                if size_of::<T>() > 0 {
+                    dealloc(
+                        ptr as *mut u8,
+                        Layout::new::<T>()
+                    );
+                }
         
                 value
             }
@@ -32,8 +106,36 @@ macro_rules! impl_erase_traits_for_unknown {
             }
         }
         
+        impl<'lifetime, T: 'lifetime$( + $traits)*> TryErase<T> for Box<dyn Unknown$( + $traits)* + 'lifetime> {
+            fn try_erase(value: T) -> Result<Self, AllocError> {
+                try_box(value).map(|boxed| boxed as Box<dyn Unknown$( + $traits)* + 'lifetime>)
+            }
+        }
+        
+        // Only for 'static values, as the target erasure doesn't track the lifetime of the value
+        #[sealed]
+        impl crate::erasure::transcode::TranscodeSource for Box<dyn Unknown$( + $traits)*> {
+            fn object(&self) -> NonNull<dyn Unknown> {
+                NonNull::from(&**self)
+            }
+            
+            unsafe fn forget_value(self) {
+                let layout = Layout::for_value(&*self);
+                
+                let ptr = Box::into_raw(self);
+                
+                // Boxes of ZSTs don't allocate
+                if layout.size() > 0 {
+                    dealloc(ptr as *mut u8, layout)
+                }
+            }
+        }
+        
         impl<'borrow, T: 'borrow$( + $traits)*> Erasure<&'borrow T> for &'borrow (dyn Unknown$( + $traits)* + 'borrow) {
             unsafe fn downcast_unchecked(self) -> &'borrow T {
+                #[cfg(feature = "verify-types")]
+                verify_type_info::<T>((*self).__type_info());
+                
                 &*(self as *const dyn Unknown as *const T)
             }
         }
@@ -46,6 +148,9 @@ macro_rules! impl_erase_traits_for_unknown {
         
         impl<'borrow, T: 'borrow$( + $traits)*> Erasure<&'borrow mut T> for &'borrow mut (dyn Unknown$( + $traits)* + 'borrow) {
             unsafe fn downcast_unchecked(self) -> &'borrow mut T {
+                #[cfg(feature = "verify-types")]
+                verify_type_info::<T>((*self).__type_info());
+                
                 &mut *(self as *mut dyn Unknown as *mut T)
             }
         }
@@ -55,9 +160,57 @@ macro_rules! impl_erase_traits_for_unknown {
                 value as &mut (dyn Unknown$( + $traits)*)
             }
         }
+        
+        impl<'lifetime, T: 'lifetime$( + $traits)*> Erasure<NonNull<T>> for NonNull<dyn Unknown$( + $traits)* + 'lifetime> {
+            unsafe fn downcast_unchecked(self) -> NonNull<T> {
+                self.cast::<T>()
+            }
+        }
+        
+        impl<'lifetime, T: 'lifetime$( + $traits)*> Erase<NonNull<T>> for NonNull<dyn Unknown$( + $traits)* + 'lifetime> {
+            fn erase(value: NonNull<T>) -> Self {
+                value as NonNull<dyn Unknown$( + $traits)*>
+            }
+        }
+        
+        impl<'lifetime, T: 'lifetime$( + $traits)*> Erasure<*mut T> for *mut (dyn Unknown$( + $traits)* + 'lifetime) {
+            unsafe fn downcast_unchecked(self) -> *mut T {
+                self as *mut T
+            }
+        }
+        
+        #[allow(unused_parens)]
+        impl<'lifetime, T: 'lifetime$( + $traits)*> Erase<*mut T> for *mut (dyn Unknown$( + $traits)* + 'lifetime) {
+            fn erase(value: *mut T) -> Self {
+                value as *mut (dyn Unknown$( + $traits)*)
+            }
+        }
     };
 }
 
+// Only for 'static values, as the source erasure doesn't track the lifetime of the value
+#[sealed]
+impl crate::erasure::transcode::TranscodeTarget for Box<dyn Unknown> {
+    unsafe fn read_from(object: NonNull<dyn Unknown>) -> Result<Self, TranscodeErrorKind> {
+        let layout = Layout::for_value(object.as_ref());
+        
+        let data = match layout.size() {
+            // Boxes of ZSTs don't allocate, but still need an aligned pointer
+            0 => without_provenance_mut::<u8>(layout.align()),
+            _ => alloc(layout)
+        };
+        
+        let Some(data) = NonNull::new(data) else {
+            return Err(TranscodeErrorKind::Alloc);
+        };
+        
+        copy_nonoverlapping(object.as_ptr() as *const u8, data.as_ptr(), layout.size());
+        
+        // SAFETY: Allocated with the layout of the value (as Box does), and initialised above.
+        Ok(Box::from_raw(UnknownMetadata::of_object(object).with_data(data).as_ptr()))
+    }
+}
+
 for_all_combinations!(impl_erase_traits_for_unknown => Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
 
 #[cfg(test)]
@@ -65,7 +218,8 @@ mod tests {
     use std::hint::black_box;
     use std::ops::Deref;
     use std::panic::{RefUnwindSafe, UnwindSafe};
-    use crate::erasure::Erasure;
+    use std::ptr::NonNull;
+    use crate::erasure::{Erase, Erasure};
     use crate::erasure::unknown::Unknown;
     
     #[test]
@@ -142,4 +296,19 @@ mod tests {
         
         assert_eq!(downcast_ref, TEST_STRING)
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn readback_pointer() {
+        let mut value = 1u32;
+        
+        let erased: NonNull<dyn Unknown> = Erase::erase(NonNull::from(&mut value));
+        
+        let mut unerased: NonNull<u32> = unsafe { erased.downcast_unchecked() };
+        unsafe { *unerased.as_mut() += 1 };
+        
+        let erased: *mut dyn Unknown = Erase::erase(unerased.as_ptr());
+        
+        let unerased: *mut u32 = unsafe { erased.downcast_unchecked() };
+        assert_eq!(unsafe { *unerased }, 2);
+    }
+}
@@ -0,0 +1,7 @@
+/// A C-like pair of values.
+/// 
+/// As the layout is `#[repr(C)]`, a pointer to the pair is also a valid pointer to its first
+/// element, which is what allows headers to be read from heap allocations without knowing the
+/// type of the second element.
+#[repr(C)]
+pub struct CPair<A, B: ?Sized>(pub A, pub B);
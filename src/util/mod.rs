@@ -1,3 +1,12 @@
 //! Utilities for [dynrsaur](crate).
 
+mod c_pair;
+pub use c_pair::CPair;
+
 mod for_all_combinations;
+
+mod try_box;
+pub use try_box::{try_box, try_box_uninit};
+
+mod with_data_pointer;
+pub use with_data_pointer::with_data_pointer;
@@ -0,0 +1,60 @@
+use std::alloc::{alloc, Layout};
+use std::mem::MaybeUninit;
+use crate::erasure::AllocError;
+
+/// Moves `value` onto the heap, as [Box::new], but returns an error instead of aborting if the
+/// allocation fails.
+/// 
+/// TODO: Replace with `Box::try_new` once stable.
+pub fn try_box<T>(value: T) -> Result<Box<T>, AllocError> {
+    let mut boxed = try_box_uninit::<T>()?;
+    
+    boxed.write(value);
+    
+    // SAFETY: Initialised above.
+    Ok(unsafe { Box::from_raw(Box::into_raw(boxed) as *mut T) })
+}
+
+/// Allocates an uninitialised `T` on the heap, returning an error instead of aborting if the
+/// allocation fails.
+/// 
+/// TODO: Replace with `Box::try_new_uninit` once stable.
+pub fn try_box_uninit<T>() -> Result<Box<MaybeUninit<T>>, AllocError> {
+    let layout = Layout::new::<T>();
+    
+    // ZSTs don't allocate
+    if layout.size() == 0 {
+        return Ok(Box::new(MaybeUninit::uninit()));
+    }
+    
+    // SAFETY: `layout` is non-zero-sized.
+    let ptr = unsafe { alloc(layout) } as *mut MaybeUninit<T>;
+    
+    if ptr.is_null() {
+        return Err(AllocError::new(layout));
+    }
+    
+    // SAFETY: `ptr` was allocated by the global allocator with the layout of a `T`, as `Box`
+    //         requires, and `MaybeUninit` doesn't need initialising.
+    Ok(unsafe { Box::from_raw(ptr) })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::{try_box, try_box_uninit};
+
+    #[test]
+    fn boxes() {
+        assert_eq!(*try_box(String::from("test")).unwrap(), "test");
+        assert_eq!(*try_box(()).unwrap(), ());
+    }
+
+    #[test]
+    fn boxes_uninit() {
+        let mut boxed = try_box_uninit::<[u64; 512]>().unwrap();
+        
+        boxed.write([7; 512]);
+        
+        assert_eq!(unsafe { boxed.assume_init_ref() }[511], 7);
+    }
+}
@@ -0,0 +1,17 @@
+/// Replaces the data pointer of a (possibly wide) pointer, keeping its metadata.
+/// 
+/// This allows a trait-object/slice pointer to be re-targeted at a different (but identically
+/// typed) value, e.g. after the value has been moved, or to give the pointer the provenance of
+/// `data`.
+/// 
+/// TODO: Use `core::ptr::from_raw_parts` once `core::ptr::Pointee` stabilised. Until then this
+///       relies on the data pointer being the first word of a wide pointer, which is checked in
+///       debug builds.
+pub fn with_data_pointer<Dyn: ?Sized>(mut ptr: *mut Dyn, data: *mut u8) -> *mut Dyn {
+    // SAFETY: Every pointer begins with its data pointer, and `ptr` is a local.
+    unsafe { *(&mut ptr as *mut *mut Dyn as *mut *mut u8) = data; }
+    
+    debug_assert_eq!(ptr as *mut u8, data, "wide pointer doesn't start with its data pointer");
+    
+    ptr
+}